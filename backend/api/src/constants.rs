@@ -1,4 +1,3 @@
 pub const EXTENSION_ORIGIN_ENV: &str = "EXTENSION_ORIGIN";
 pub const EXTENSION_NAME_ENV: &str = "EXTENSION_NAME";
-pub const DYNAMODB_TABLE_ENV: &str = "DYNAMODB_TABLE_NAME";
 pub const X_EXTENSION_NAME_HEADER: &str = "x-extension-name";
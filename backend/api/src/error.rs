@@ -8,6 +8,7 @@ use serde::Serialize;
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "snake_case")]
 enum ErrorType {
+    BadRequest,
     Forbidden,
     InternalError,
 }
@@ -27,6 +28,10 @@ impl ErrorResponse {
         }
     }
 
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(ErrorType::BadRequest, message)
+    }
+
     pub fn forbidden() -> Self {
         Self::new(ErrorType::Forbidden, "Access is forbidden.")
     }
@@ -39,6 +44,7 @@ impl ErrorResponse {
 impl IntoResponse for ErrorResponse {
     fn into_response(self) -> Response {
         let status = match self.error {
+            ErrorType::BadRequest => StatusCode::BAD_REQUEST,
             ErrorType::Forbidden => StatusCode::FORBIDDEN,
             ErrorType::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
         };
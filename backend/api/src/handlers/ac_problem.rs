@@ -1,30 +1,36 @@
 use crate::error::ErrorResponse;
 use crate::extractors::VerifiedExtension;
+use atcoder_ids::{ProblemId, UserId};
 use axum::{
     Json,
     extract::{Path, State},
     response::IntoResponse,
 };
-use ddb_client::{DdbError, DdbService};
+use ddb_client::{AcStore, DdbError};
 use lambda_http::tracing;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
 struct Response {
-    problem_ids: Vec<String>,
+    problem_ids: Vec<ProblemId>,
 }
 
 /// Handles GET /users/{user_id}/problems
 /// Returns a list of AC problems for the given user.
-pub async fn get_ac_problems(
+pub async fn get_ac_problems<S: AcStore>(
     VerifiedExtension: VerifiedExtension,
     Path(user_id): Path<String>,
-    State(ddb_service): State<DdbService>,
+    State(store): State<S>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     tracing::info!("Received request for user_id: {}", user_id);
 
-    let ac_problems = match ddb_service.get_user_ac_problems(&user_id).await {
-        Ok(record) => record.ac_problems,
+    let user_id = UserId::parse(&user_id).map_err(|err| {
+        tracing::warn!("Rejected request: {}", err);
+        ErrorResponse::bad_request(err.to_string())
+    })?;
+
+    let ac_problems = match store.get_user_ac_problems(&user_id).await {
+        Ok(record) => record.ac_problems.into_keys().collect(),
         Err(DdbError::NotFound) => {
             tracing::warn!("User ID {} not found", user_id);
             Vec::new()
@@ -45,3 +51,49 @@ pub async fn get_ac_problems(
         problem_ids: ac_problems,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::to_bytes, http::StatusCode};
+    use ddb_client::MemoryStore;
+
+    async fn call(store: MemoryStore, user_id: &str) -> (StatusCode, String) {
+        let response = get_ac_problems(VerifiedExtension, Path(user_id.to_string()), State(store))
+            .await
+            .into_response();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn returns_stored_ac_problems() {
+        let store = MemoryStore::new();
+        let user_id = UserId::parse("tourist").unwrap();
+        store
+            .add_ac_problems(
+                &user_id,
+                [(ProblemId::parse("abc407_a").unwrap(), None)].into(),
+            )
+            .await
+            .unwrap();
+
+        let (status, body) = call(store, "tourist").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, r#"{"problem_ids":["abc407_a"]}"#);
+    }
+
+    #[tokio::test]
+    async fn unknown_user_has_no_ac_problems() {
+        let (status, body) = call(MemoryStore::new(), "unknown").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, r#"{"problem_ids":[]}"#);
+    }
+
+    #[tokio::test]
+    async fn malformed_user_id_is_rejected() {
+        let (status, _) = call(MemoryStore::new(), "not-a-user").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
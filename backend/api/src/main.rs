@@ -8,8 +8,8 @@ use axum::{
     http::{HeaderValue, header},
     routing::get,
 };
-use constants::{DYNAMODB_TABLE_ENV, EXTENSION_ORIGIN_ENV, X_EXTENSION_NAME_HEADER};
-use ddb_client::DdbService;
+use constants::{EXTENSION_ORIGIN_ENV, X_EXTENSION_NAME_HEADER};
+use ddb_client::StoreBackend;
 use handlers::get_ac_problems;
 use lambda_http::{Error, http::Method, run, tracing};
 use std::env;
@@ -20,10 +20,8 @@ async fn main() -> Result<(), Error> {
     // Required to enable CloudWatch error logging by the runtime
     tracing::init_default_subscriber();
 
-    // Load DynamoDB table name and initialize service
-    let table_name = env::var(DYNAMODB_TABLE_ENV)
-        .unwrap_or_else(|_| panic!("Environment variable {} is not set", DYNAMODB_TABLE_ENV));
-    let ddb_service = DdbService::from_env(table_name).await;
+    // Initialize the store selected by STORE_BACKEND (DynamoDB by default)
+    let store = StoreBackend::from_env().await?;
 
     // Set up CORS layer with multiple origins
     let extension_origins = env::var(EXTENSION_ORIGIN_ENV)
@@ -46,9 +44,12 @@ async fn main() -> Result<(), Error> {
 
     // Setup router
     let app = Router::new()
-        .route("/users/{user_id}/problems", get(get_ac_problems))
+        .route(
+            "/users/{user_id}/problems",
+            get(get_ac_problems::<StoreBackend>),
+        )
         .layer(cors_layer)
-        .with_state(ddb_service);
+        .with_state(store);
 
     run(app).await
 }
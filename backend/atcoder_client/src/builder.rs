@@ -0,0 +1,489 @@
+use crate::client::AtCoderClient;
+use crate::constants::{
+    ATCODER_BASE_URL, DEFAULT_RATE_LIMIT_BURST, DEFAULT_REQUESTS_PER_SECOND, REVEL_SESSION_COOKIE,
+    TEST_CONTEST_ID,
+};
+use crate::cookie_file::CookieFile;
+use crate::error::AtCoderClientError;
+use crate::fixture::FixtureStore;
+use crate::models::SubmissionFilter;
+use crate::rate_limiter::RateLimiter;
+use cookie_store::CookieStore;
+use reqwest::{Client, Url, redirect::Policy};
+use reqwest_cookie_store::CookieStoreMutex;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+/// How the REVEL_SESSION is verified when the client is built.
+#[derive(Clone, Debug)]
+enum SessionVerification {
+    /// Access the submissions page of `TEST_CONTEST_ID`.
+    Default,
+    /// Access the given URL (absolute or relative to the base URL).
+    Url(String),
+    /// Do not verify the session.
+    Skip,
+}
+
+/// Builder for `AtCoderClient` with configurable base URL, user agent, timeouts and session handling.
+#[derive(Clone, Debug)]
+pub struct AtCoderClientBuilder {
+    base_url: String,
+    user_agent: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    gzip: bool,
+    requests_per_second: f64,
+    burst: u32,
+    revel_session: Option<String>,
+    session_verification: SessionVerification,
+    cookie_file: Option<CookieFile>,
+    fixtures: Option<FixtureStore>,
+}
+
+impl Default for AtCoderClientBuilder {
+    fn default() -> Self {
+        Self {
+            base_url: ATCODER_BASE_URL.to_string(),
+            user_agent: None,
+            timeout: None,
+            connect_timeout: None,
+            gzip: true,
+            requests_per_second: DEFAULT_REQUESTS_PER_SECOND,
+            burst: DEFAULT_RATE_LIMIT_BURST,
+            revel_session: None,
+            session_verification: SessionVerification::Default,
+            cookie_file: None,
+            fixtures: None,
+        }
+    }
+}
+
+impl AtCoderClientBuilder {
+    /// Sets the base URL of the AtCoder site (e.g. a local mock server in tests).
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets the total timeout of each request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for establishing a connection.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Enables or disables gzip decompression of responses (enabled by default).
+    pub fn gzip(mut self, enable: bool) -> Self {
+        self.gzip = enable;
+        self
+    }
+
+    /// Sets the rate limit shared by all clones of the built client.
+    /// `burst` requests may be sent back-to-back before pacing at `requests_per_second`.
+    /// `build` fails unless `requests_per_second` is positive and finite and `burst` is at least 1.
+    pub fn rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.requests_per_second = requests_per_second;
+        self.burst = burst;
+        self
+    }
+
+    /// Sets the REVEL_SESSION cookie used for authenticated requests.
+    pub fn revel_session(mut self, session: impl Into<String>) -> Self {
+        self.revel_session = Some(session.into());
+        self
+    }
+
+    /// Verifies the session by accessing the given URL instead of the default submissions page.
+    /// Relative URLs are resolved against the base URL.
+    pub fn session_verification_url(mut self, url: impl Into<String>) -> Self {
+        self.session_verification = SessionVerification::Url(url.into());
+        self
+    }
+
+    /// Skips the session verification request when building the client.
+    pub fn skip_session_verification(mut self) -> Self {
+        self.session_verification = SessionVerification::Skip;
+        self
+    }
+
+    /// Persists the cookie jar to `path` and restores it from there on the next build.
+    /// A REVEL_SESSION set on this builder takes precedence over the restored one.
+    pub fn cookie_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cookie_file = Some(CookieFile::new(path));
+        self
+    }
+
+    /// Saves every fetched page under `dir`, keyed by URL, for later replay.
+    pub fn record_fixtures(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.fixtures = Some(FixtureStore::record(dir));
+        self
+    }
+
+    /// Serves pages from `dir` (as saved by `record_fixtures`) instead of the network.
+    /// Requests without a recorded fixture fail with `FixtureNotFound`.
+    pub fn replay_fixtures(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.fixtures = Some(FixtureStore::replay(dir));
+        self
+    }
+
+    /// Builds the client.
+    /// If a REVEL_SESSION is set or restored from the cookie file, it is verified unless
+    /// verification is skipped, and the cookie jar is saved back to the cookie file.
+    pub async fn build(self) -> Result<AtCoderClient, AtCoderClientError> {
+        let client = self.build_client(true)?;
+
+        if self.revel_session.is_some() || client.revel_session().is_some() {
+            match &self.session_verification {
+                SessionVerification::Default => {
+                    let url = client.contest_submissions_url(
+                        TEST_CONTEST_ID,
+                        1,
+                        &SubmissionFilter::default(),
+                    );
+                    client.verify_session(&url).await?;
+                }
+                SessionVerification::Url(url) => client.verify_session(url).await?,
+                SessionVerification::Skip => {}
+            }
+            client.save_cookies()?;
+        }
+
+        Ok(client)
+    }
+
+    /// Builds the client by logging in with the given username and password.
+    /// Any REVEL_SESSION set on this builder or stored in the cookie file is replaced by
+    /// the issued one, which is then saved to the cookie file.
+    pub async fn login(
+        mut self,
+        username: &str,
+        password: &str,
+    ) -> Result<AtCoderClient, AtCoderClientError> {
+        self.revel_session = None;
+        let client = self.build_client(false)?;
+
+        client.login_with_form(username, password).await?;
+        client.save_cookies()?;
+
+        Ok(client)
+    }
+
+    /// Builds the underlying HTTP client and cookie jar.
+    /// The jar starts from the cookie file when `restore_cookies` is set and the file exists.
+    fn build_client(&self, restore_cookies: bool) -> Result<AtCoderClient, AtCoderClientError> {
+        let base_url = Url::parse(&self.base_url)
+            .map_err(|e| AtCoderClientError::InvalidUrl(format!("{}: {}", self.base_url, e)))?;
+
+        let mut store = match &self.cookie_file {
+            Some(cookie_file) if restore_cookies => cookie_file.load()?,
+            _ => CookieStore::default(),
+        };
+        if let Some(session) = &self.revel_session {
+            let cookie_header = format!("{}={}; Path=/", REVEL_SESSION_COOKIE, session);
+            // An unparsable value is never sent, so the verification reports it as invalid
+            let _ = store.parse(&cookie_header, &base_url);
+        }
+        let jar = Arc::new(CookieStoreMutex::new(store));
+
+        let mut builder = Client::builder()
+            .cookie_provider(jar.clone())
+            .redirect(Policy::none())
+            .gzip(self.gzip);
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+
+        if !(self.requests_per_second.is_finite() && self.requests_per_second > 0.0) {
+            return Err(AtCoderClientError::InvalidRateLimit(format!(
+                "requests_per_second must be positive and finite, got {}",
+                self.requests_per_second
+            )));
+        }
+        if self.burst == 0 {
+            return Err(AtCoderClientError::InvalidRateLimit(
+                "burst must be at least 1".to_string(),
+            ));
+        }
+        let rate_limiter = RateLimiter::new(self.requests_per_second, self.burst);
+
+        Ok(AtCoderClient::new(
+            builder.build()?,
+            jar,
+            self.cookie_file.clone(),
+            base_url,
+            rate_limiter,
+            self.fixtures.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use wiremock::matchers::{body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const LOGIN_PAGE: &str = r#"<html><body><form action="" method="POST">
+        <input type="hidden" name="csrf_token" value="test-csrf-token">
+        </form></body></html>"#;
+
+    /// Starts a mock server serving the login form.
+    async fn start_login_server() -> MockServer {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/login"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("set-cookie", "REVEL_SESSION=anonymous; Path=/; HttpOnly")
+                    .set_body_string(LOGIN_PAGE),
+            )
+            .mount(&server)
+            .await;
+
+        server
+    }
+
+    #[tokio::test]
+    async fn build_with_valid_session_verifies_against_custom_url() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/home"))
+            .and(header("cookie", "REVEL_SESSION=valid"))
+            .and(header("user-agent", "adt-sync-test"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .user_agent("adt-sync-test")
+            .timeout(Duration::from_secs(5))
+            .connect_timeout(Duration::from_secs(1))
+            .revel_session("valid")
+            .session_verification_url("/home")
+            .build()
+            .await
+            .expect("client should be built");
+
+        assert_eq!(client.revel_session().as_deref(), Some("valid"));
+    }
+
+    #[tokio::test]
+    async fn build_with_expired_session_returns_invalid_session_error() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path(format!("/contests/{}/submissions", TEST_CONTEST_ID)))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/login"))
+            .mount(&server)
+            .await;
+
+        let result = AtCoderClient::builder()
+            .base_url(server.uri())
+            .revel_session("expired")
+            .build()
+            .await;
+
+        assert!(matches!(result, Err(AtCoderClientError::InvalidSession)));
+    }
+
+    #[tokio::test]
+    async fn build_with_invalid_rate_limit_returns_invalid_rate_limit_error() {
+        for (requests_per_second, burst) in [
+            (0.0, 1),
+            (-1.0, 1),
+            (f64::NAN, 1),
+            (f64::INFINITY, 1),
+            (1.0, 0),
+        ] {
+            let result = AtCoderClient::builder()
+                .rate_limit(requests_per_second, burst)
+                .build()
+                .await;
+
+            assert!(
+                matches!(result, Err(AtCoderClientError::InvalidRateLimit(_))),
+                "rate limit ({requests_per_second}, {burst}) should be rejected"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn build_with_invalid_base_url_returns_invalid_url_error() {
+        let result = AtCoderClient::builder()
+            .base_url("not a url")
+            .skip_session_verification()
+            .build()
+            .await;
+
+        assert!(matches!(result, Err(AtCoderClientError::InvalidUrl(_))));
+    }
+
+    #[tokio::test]
+    async fn login_with_valid_credentials_captures_session() {
+        let server = start_login_server().await;
+
+        Mock::given(method("POST"))
+            .and(path("/login"))
+            .and(body_string_contains("username=test1"))
+            .and(body_string_contains("password=correct"))
+            .and(body_string_contains("csrf_token=test-csrf-token"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header("location", "/home")
+                    .insert_header("set-cookie", "REVEL_SESSION=logged-in; Path=/; HttpOnly"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .login("test1", "correct")
+            .await
+            .expect("login should succeed");
+
+        assert_eq!(client.revel_session().as_deref(), Some("logged-in"));
+    }
+
+    #[tokio::test]
+    async fn login_with_invalid_credentials_returns_invalid_credentials_error() {
+        let server = start_login_server().await;
+
+        Mock::given(method("POST"))
+            .and(path("/login"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/login"))
+            .mount(&server)
+            .await;
+
+        let result = AtCoderClient::builder()
+            .base_url(server.uri())
+            .login("test1", "wrong")
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(AtCoderClientError::InvalidCredentials)
+        ));
+    }
+
+    #[tokio::test]
+    async fn login_redirect_without_new_session_returns_invalid_session_error() {
+        let server = start_login_server().await;
+
+        // Redirects away from /login but keeps the anonymous session
+        Mock::given(method("POST"))
+            .and(path("/login"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/home"))
+            .mount(&server)
+            .await;
+
+        let result = AtCoderClient::builder()
+            .base_url(server.uri())
+            .login("test1", "correct")
+            .await;
+
+        assert!(matches!(result, Err(AtCoderClientError::InvalidSession)));
+    }
+
+    #[tokio::test]
+    async fn login_persists_session_to_cookie_file_for_next_build() {
+        let server = start_login_server().await;
+        let dir = tempfile::tempdir().unwrap();
+        let cookie_path = dir.path().join("cookies.json");
+
+        Mock::given(method("POST"))
+            .and(path("/login"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header("location", "/home")
+                    .insert_header(
+                        "set-cookie",
+                        "REVEL_SESSION=logged-in; Path=/; Expires=Wed, 01 Jan 2100 00:00:00 GMT; HttpOnly",
+                    ),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/home"))
+            .and(header("cookie", "REVEL_SESSION=logged-in"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .cookie_file(&cookie_path)
+            .login("test1", "correct")
+            .await
+            .expect("login should succeed");
+        let expires_at = DateTime::parse_from_rfc3339("2100-01-01T00:00:00Z").unwrap();
+        assert_eq!(client.session_expires_at(), Some(expires_at.to_utc()));
+        assert!(cookie_path.exists());
+
+        // The next run restores and verifies the saved session without logging in again
+        let restored = AtCoderClient::builder()
+            .base_url(server.uri())
+            .cookie_file(&cookie_path)
+            .session_verification_url("/home")
+            .build()
+            .await
+            .expect("restored session should be verified");
+        assert_eq!(restored.revel_session().as_deref(), Some("logged-in"));
+        assert_eq!(restored.session_expires_at(), Some(expires_at.to_utc()));
+    }
+
+    #[tokio::test]
+    async fn build_with_revoked_session_from_cookie_file_returns_invalid_session_error() {
+        let server = MockServer::start().await;
+        let dir = tempfile::tempdir().unwrap();
+        let cookie_path = dir.path().join("cookies.json");
+
+        let url = Url::parse(&server.uri()).unwrap();
+        let mut store = CookieStore::default();
+        store
+            .parse(
+                "REVEL_SESSION=revoked; Path=/; Expires=Wed, 01 Jan 2100 00:00:00 GMT",
+                &url,
+            )
+            .unwrap();
+        CookieFile::new(&cookie_path).save(&store).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/home"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/login"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = AtCoderClient::builder()
+            .base_url(server.uri())
+            .cookie_file(&cookie_path)
+            .session_verification_url("/home")
+            .build()
+            .await;
+
+        assert!(matches!(result, Err(AtCoderClientError::InvalidSession)));
+    }
+}
@@ -1,101 +1,775 @@
-use crate::constants::{ATCODER_BASE_URL, TEST_CONTEST_ID};
+use crate::builder::AtCoderClientBuilder;
+use crate::constants::REVEL_SESSION_COOKIE;
+use crate::cookie_file::CookieFile;
 use crate::error::AtCoderClientError;
-use crate::models::{Contest, Submission};
-use crate::scraper::{scrape_contest_page, scrape_submission_page};
-use reqwest::{Client, StatusCode, Url, cookie::Jar, redirect::Policy};
-use std::{str::FromStr, sync::Arc};
+use crate::fixture::{Fixture, FixtureStore};
+use crate::models::{
+    Contest, ContestArchiveQuery, ContestSchedule, ContestTask, ScrapedPage, Submission,
+    SubmissionDetail, SubmissionFilter,
+};
+use crate::rate_limiter::{RateLimitMetrics, RateLimiter};
+use crate::scraper::{
+    detect_interstitial_page, scrape_contest_page, scrape_contest_page_with_pagination,
+    scrape_contest_schedule_page, scrape_login_page, scrape_submission_detail_page,
+    scrape_submission_page, scrape_submission_page_lenient, scrape_task_page,
+};
+use atcoder_ids::{ContestId, SubmissionId};
+use chrono::{DateTime, Utc};
+use cookie_store::CookieExpiration;
+use futures::{Stream, TryStreamExt, stream};
+use reqwest::{Client, RequestBuilder, Response, StatusCode, Url, header};
+use reqwest_cookie_store::CookieStoreMutex;
+use std::{future::Future, sync::Arc, time::Duration};
 
 /// A client for scraping AtCoder pages using an authenticated REVEL_SESSION.
+/// Clones share the cookie jar and the rate limiter.
 #[derive(Clone)]
 pub struct AtCoderClient {
     client: Client,
+    jar: Arc<CookieStoreMutex>,
+    cookie_file: Option<CookieFile>,
+    base_url: Url,
+    rate_limiter: Arc<RateLimiter>,
+    fixtures: Option<FixtureStore>,
 }
 
 impl AtCoderClient {
+    /// Returns a builder to configure the base URL, timeouts and session handling.
+    pub fn builder() -> AtCoderClientBuilder {
+        AtCoderClientBuilder::default()
+    }
+
     /// Creates a new client using the given REVEL_SESSION cookie.
     /// Verifies the session by accessing a known submissions page.
     pub async fn from_revel_session(session: &str) -> Result<Self, AtCoderClientError> {
-        let cookie_header = format!("REVEL_SESSION={}", session);
-        let url = Url::from_str(ATCODER_BASE_URL).expect("Hardcoded base URL should be valid");
+        Self::builder().revel_session(session).build().await
+    }
+
+    /// Creates a new client by logging in with the given username and password.
+    /// The REVEL_SESSION cookie issued on success is kept for subsequent requests.
+    pub async fn login(username: &str, password: &str) -> Result<Self, AtCoderClientError> {
+        Self::builder().login(username, password).await
+    }
+
+    /// Returns the current REVEL_SESSION cookie value, if any.
+    pub fn revel_session(&self) -> Option<String> {
+        let jar = self.jar.lock().ok()?;
+
+        jar.get_request_values(&self.base_url)
+            .find(|(name, _)| *name == REVEL_SESSION_COOKIE)
+            .map(|(_, value)| value.to_owned())
+    }
+
+    /// Returns when the current REVEL_SESSION cookie expires.
+    /// `None` if there is no session or it is a non-persistent cookie (e.g. one set manually).
+    pub fn session_expires_at(&self) -> Option<DateTime<Utc>> {
+        let jar = self.jar.lock().ok()?;
+        let cookie = jar
+            .matches(&self.base_url)
+            .into_iter()
+            .find(|cookie| cookie.name() == REVEL_SESSION_COOKIE)?;
+
+        match cookie.expires {
+            CookieExpiration::AtUtc(expires_at) => {
+                DateTime::from_timestamp(expires_at.unix_timestamp(), 0)
+            }
+            CookieExpiration::SessionEnd => None,
+        }
+    }
 
-        let jar = Jar::default();
-        jar.add_cookie_str(&cookie_header, &url);
+    /// Saves the cookie jar to the file set by `AtCoderClientBuilder::cookie_file`, if any.
+    /// The jar is saved automatically after the session is verified or issued by login;
+    /// call this again to keep cookies refreshed during the run.
+    pub fn save_cookies(&self) -> Result<(), AtCoderClientError> {
+        let Some(cookie_file) = &self.cookie_file else {
+            return Ok(());
+        };
 
-        let client = Client::builder()
-            .cookie_provider(Arc::new(jar))
-            .redirect(Policy::none())
-            .build()?;
+        let jar = self.jar.lock().map_err(|_| {
+            AtCoderClientError::CookieFileError("cookie jar lock is poisoned".to_string())
+        })?;
+        cookie_file.save(&jar)
+    }
 
-        let this = Self { client };
+    /// Returns the time requests have spent waiting on the shared rate limiter.
+    pub fn rate_limit_metrics(&self) -> RateLimitMetrics {
+        self.rate_limiter.metrics()
+    }
 
-        let test_url = Self::contest_submissions_url(TEST_CONTEST_ID, 1);
-        this.get_html(&test_url).await?;
+    /// Wraps an HTTP client configured by `AtCoderClientBuilder`.
+    pub(crate) fn new(
+        client: Client,
+        jar: Arc<CookieStoreMutex>,
+        cookie_file: Option<CookieFile>,
+        base_url: Url,
+        rate_limiter: RateLimiter,
+        fixtures: Option<FixtureStore>,
+    ) -> Self {
+        Self {
+            client,
+            jar,
+            cookie_file,
+            base_url,
+            rate_limiter: Arc::new(rate_limiter),
+            fixtures,
+        }
+    }
 
-        Ok(this)
+    /// Verifies the session by accessing the given URL (absolute or relative to the base URL).
+    pub(crate) async fn verify_session(&self, url: &str) -> Result<(), AtCoderClientError> {
+        let url = self
+            .base_url
+            .join(url)
+            .map_err(|e| AtCoderClientError::InvalidUrl(e.to_string()))?;
+        self.get_html(url.as_str()).await?;
+        Ok(())
+    }
+
+    /// Logs in using the login form and keeps the issued session cookie.
+    pub(crate) async fn login_with_form(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<(), AtCoderClientError> {
+        // The login page issues a temporary session bound to the CSRF token
+        let login_url = self.url("/login");
+        let html = self.get_html(&login_url).await?;
+        let csrf_token = scrape_login_page(&html)?;
+        let anonymous_session = self.revel_session();
+
+        let request = self.client.post(&login_url).form(&[
+            ("username", username),
+            ("password", password),
+            ("csrf_token", &csrf_token),
+        ]);
+        let response = self.send(request).await?;
+
+        // AtCoder redirects back to /login (or re-renders it) when the credentials are rejected
+        let status = response.status();
+        if status.is_redirection() {
+            let location = response
+                .headers()
+                .get(header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default();
+            if location.contains("/login") {
+                return Err(AtCoderClientError::InvalidCredentials);
+            }
+        } else if status.is_success() {
+            return Err(AtCoderClientError::InvalidCredentials);
+        } else {
+            return Err(Self::response_error(&response));
+        }
+
+        // A successful login issues a new session in place of the anonymous one
+        let session = self.revel_session();
+        if session.is_none() || session == anonymous_session {
+            return Err(AtCoderClientError::InvalidSession);
+        }
+
+        Ok(())
     }
 
     /// Fetches and parses the ADT contests archive page.
     pub async fn fetch_adt_contests(&self, page: u32) -> Result<Vec<Contest>, AtCoderClientError> {
-        let url = Self::adt_archive_url(page);
+        self.fetch_contest_archive(&ContestArchiveQuery::adt(), page)
+            .await
+    }
+
+    /// Fetches and parses the ADT contests archive page along with its pagination.
+    pub async fn fetch_adt_contests_page(
+        &self,
+        page: u32,
+    ) -> Result<ScrapedPage<Contest>, AtCoderClientError> {
+        self.fetch_contest_archive_page(&ContestArchiveQuery::adt(), page)
+            .await
+    }
+
+    /// Fetches and parses the running and upcoming contests from the contest list page.
+    pub async fn fetch_contest_schedule(&self) -> Result<ContestSchedule, AtCoderClientError> {
+        let url = self.url("/contests/?lang=ja");
+        let html = self.get_html(&url).await?;
+
+        let schedule = scrape_contest_schedule_page(&html)?;
+        Ok(schedule)
+    }
+
+    /// Fetches the ADT contests that are running or scheduled to start, soonest first.
+    pub async fn fetch_upcoming_adt_contests(&self) -> Result<Vec<Contest>, AtCoderClientError> {
+        let schedule = self.fetch_contest_schedule().await?;
+
+        Ok(schedule
+            .active
+            .into_iter()
+            .chain(schedule.upcoming)
+            .filter(Contest::is_adt)
+            .collect())
+    }
+
+    /// Fetches and parses a contest archive page matching `query`.
+    pub async fn fetch_contest_archive(
+        &self,
+        query: &ContestArchiveQuery,
+        page: u32,
+    ) -> Result<Vec<Contest>, AtCoderClientError> {
+        let url = self.contest_archive_url(query, page);
         let html = self.get_html(&url).await?;
 
         let contests = scrape_contest_page(&html)?;
         Ok(contests)
     }
 
+    /// Fetches and parses a contest archive page matching `query` along with its pagination.
+    pub async fn fetch_contest_archive_page(
+        &self,
+        query: &ContestArchiveQuery,
+        page: u32,
+    ) -> Result<ScrapedPage<Contest>, AtCoderClientError> {
+        let url = self.contest_archive_url(query, page);
+        let html = self.get_html(&url).await?;
+
+        let contests = scrape_contest_page_with_pagination(&html)?;
+        Ok(contests)
+    }
+
     /// Fetches and parses the submissions page for a given contest.
+    /// Only submissions matching `filter` are requested from AtCoder.
     pub async fn fetch_submissions(
         &self,
-        contest_id: &str,
+        contest_id: &ContestId,
         page: u32,
+        filter: &SubmissionFilter,
     ) -> Result<Vec<Submission>, AtCoderClientError> {
-        let url = Self::contest_submissions_url(contest_id, page);
+        let url = self.contest_submissions_url(contest_id.as_str(), page, filter);
         let html = self.get_html(&url).await?;
 
         let submissions = scrape_submission_page(&html, contest_id)?;
         Ok(submissions)
     }
 
+    /// Fetches and parses the submissions page like [`fetch_submissions`](Self::fetch_submissions),
+    /// but skips malformed rows and returns them as rejected instead of failing the page.
+    /// The result also carries the page's position from the pagination widget.
+    pub async fn fetch_submissions_lenient(
+        &self,
+        contest_id: &ContestId,
+        page: u32,
+        filter: &SubmissionFilter,
+    ) -> Result<ScrapedPage<Submission>, AtCoderClientError> {
+        let url = self.contest_submissions_url(contest_id.as_str(), page, filter);
+        let html = self.get_html(&url).await?;
+
+        let submissions = scrape_submission_page_lenient(&html, contest_id)?;
+        Ok(submissions)
+    }
+
+    /// Fetches and parses the detail page of a single submission,
+    /// including its source code and per-test-case verdicts.
+    pub async fn fetch_submission_detail(
+        &self,
+        contest_id: &ContestId,
+        submission_id: SubmissionId,
+    ) -> Result<SubmissionDetail, AtCoderClientError> {
+        let url = self.submission_detail_url(contest_id.as_str(), submission_id);
+        let html = self.get_html(&url).await?;
+
+        let detail = scrape_submission_detail_page(&html, contest_id, submission_id)?;
+        Ok(detail)
+    }
+
+    /// Fetches and parses the task list page for a given contest.
+    pub async fn fetch_contest_tasks(
+        &self,
+        contest_id: &ContestId,
+    ) -> Result<Vec<ContestTask>, AtCoderClientError> {
+        let url = self.contest_tasks_url(contest_id.as_str());
+        let html = self.get_html(&url).await?;
+
+        let tasks = scrape_task_page(&html)?;
+        Ok(tasks)
+    }
+
+    /// Streams the ADT contests from the archive, newest first, fetching pages lazily.
+    /// The stream ends after the last page, or after yielding the first error.
+    pub fn adt_contests_stream(
+        &self,
+    ) -> impl Stream<Item = Result<Contest, AtCoderClientError>> + use<> {
+        self.contest_archive_stream(&ContestArchiveQuery::adt())
+    }
+
+    /// Streams the archived contests matching `query`, newest first, fetching pages lazily.
+    /// The stream ends after the last page, or after yielding the first error.
+    pub fn contest_archive_stream(
+        &self,
+        query: &ContestArchiveQuery,
+    ) -> impl Stream<Item = Result<Contest, AtCoderClientError>> + use<> {
+        let client = self.clone();
+        let query = query.clone();
+        paginate(move |page| {
+            let client = client.clone();
+            let query = query.clone();
+            async move { client.fetch_contest_archive(&query, page).await }
+        })
+    }
+
+    /// Streams the submissions of a contest matching `filter`, newest first, fetching pages lazily.
+    /// The stream ends after the last page, or after yielding the first error.
+    pub fn submissions_stream(
+        &self,
+        contest_id: &ContestId,
+        filter: &SubmissionFilter,
+    ) -> impl Stream<Item = Result<Submission, AtCoderClientError>> + use<> {
+        let client = self.clone();
+        let contest_id = contest_id.clone();
+        let filter = filter.clone();
+        paginate(move |page| {
+            let client = client.clone();
+            let contest_id = contest_id.clone();
+            let filter = filter.clone();
+            async move { client.fetch_submissions(&contest_id, page, &filter).await }
+        })
+    }
+
     /// Performs a GET request and returns the HTML as a string.
+    /// Maintenance notices and anti-bot challenges served with HTTP 200 are returned as errors,
+    /// so they are never mistaken for an empty page.
     async fn get_html(&self, url: &str) -> Result<String, AtCoderClientError> {
-        let response = self
-            .client
-            .get(url)
-            .header("accept", "text/html")
-            .send()
-            .await?;
+        let html = self.fetch_html(url).await?;
+        detect_interstitial_page(&html)?;
+        Ok(html)
+    }
+
+    /// Performs a GET request and returns the response body as a string.
+    /// In replay mode the page is served from the fixture directory instead of the network,
+    /// and in record mode every page and non-retryable error status is saved there.
+    async fn fetch_html(&self, url: &str) -> Result<String, AtCoderClientError> {
+        let fixture_url =
+            Url::parse(url).map_err(|e| AtCoderClientError::InvalidUrl(e.to_string()))?;
+
+        if let Some(fixtures) = &self.fixtures
+            && fixtures.is_replay()
+        {
+            return match fixtures.load(&fixture_url)? {
+                Fixture::Page(html) => Ok(html),
+                Fixture::Status(status) => Err(Self::status_error(status, None)),
+            };
+        }
+
+        let request = self.client.get(url).header("accept", "text/html");
+        let response = self.send(request).await?;
 
         let status = response.status();
         if !status.is_success() {
-            return Err(match status {
-                // Redirects (e.g., to /login) indicate an invalid or expired session
-                StatusCode::FOUND | StatusCode::UNAUTHORIZED => AtCoderClientError::InvalidSession,
-                StatusCode::FORBIDDEN => AtCoderClientError::Forbidden,
-                StatusCode::NOT_FOUND => AtCoderClientError::NotFound,
-                status if status.is_server_error() => AtCoderClientError::ServerError(status),
-                _ => AtCoderClientError::UnexpectedHttpStatus(status),
-            });
+            let error = Self::response_error(&response);
+            if let Some(fixtures) = &self.fixtures
+                && !error.is_retryable()
+            {
+                fixtures.save(&fixture_url, &Fixture::Status(status))?;
+            }
+            return Err(error);
         }
 
-        response
+        let html = response
             .text()
             .await
-            .map_err(AtCoderClientError::ReqwestError)
+            .map_err(AtCoderClientError::ReqwestError)?;
+
+        if let Some(fixtures) = &self.fixtures {
+            fixtures.save(&fixture_url, &Fixture::Page(html.clone()))?;
+        }
+
+        Ok(html)
+    }
+
+    /// Sends a request once the shared rate limiter allows it.
+    async fn send(&self, request: RequestBuilder) -> Result<Response, AtCoderClientError> {
+        self.rate_limiter.acquire().await;
+        Ok(request.send().await?)
+    }
+
+    /// Maps a non-successful HTTP response to the corresponding error.
+    fn response_error(response: &Response) -> AtCoderClientError {
+        Self::status_error(response.status(), Self::retry_after(response))
+    }
+
+    /// Maps a non-successful HTTP status to the corresponding error.
+    fn status_error(status: StatusCode, retry_after: Option<Duration>) -> AtCoderClientError {
+        match status {
+            // Redirects (e.g., to /login) indicate an invalid or expired session
+            StatusCode::FOUND | StatusCode::UNAUTHORIZED => AtCoderClientError::InvalidSession,
+            StatusCode::FORBIDDEN => AtCoderClientError::Forbidden,
+            StatusCode::TOO_MANY_REQUESTS => AtCoderClientError::TooManyRequests { retry_after },
+            StatusCode::NOT_FOUND => AtCoderClientError::NotFound,
+            status if status.is_server_error() => AtCoderClientError::ServerError(status),
+            status => AtCoderClientError::UnexpectedHttpStatus(status),
+        }
+    }
+
+    /// Parses the `Retry-After` header given either in seconds or as an HTTP date.
+    fn retry_after(response: &Response) -> Option<Duration> {
+        let value = response
+            .headers()
+            .get(header::RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim();
+
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+
+        let retry_at = DateTime::parse_from_rfc2822(value).ok()?;
+        let delay = retry_at.with_timezone(&Utc) - Utc::now();
+        Some(delay.to_std().unwrap_or(Duration::ZERO))
+    }
+
+    /// Constructs an absolute URL from a path on the AtCoder site.
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url.as_str().trim_end_matches('/'), path)
+    }
+
+    /// Constructs the URL for a contest archive page matching `query`.
+    pub(crate) fn contest_archive_url(&self, query: &ContestArchiveQuery, page: u32) -> String {
+        let mut url = Url::parse(&self.url("/contests/archive"))
+            .expect("Contest archive URL should be valid");
+
+        url.query_pairs_mut()
+            .extend_pairs(query.query_pairs())
+            .append_pair("page", &page.to_string());
+
+        url.into()
+    }
+
+    /// Constructs the URL for a contest's task list page.
+    fn contest_tasks_url(&self, contest_id: &str) -> String {
+        self.url(&format!("/contests/{}/tasks?lang=ja", contest_id))
+    }
+
+    /// Constructs the URL for a submission's detail page.
+    fn submission_detail_url(&self, contest_id: &str, submission_id: SubmissionId) -> String {
+        self.url(&format!(
+            "/contests/{}/submissions/{}?lang=ja",
+            contest_id, submission_id
+        ))
+    }
+
+    /// Constructs the URL for a contest's submissions page with the given filter applied.
+    pub(crate) fn contest_submissions_url(
+        &self,
+        contest_id: &str,
+        page: u32,
+        filter: &SubmissionFilter,
+    ) -> String {
+        let mut url = Url::parse(&self.url(&format!("/contests/{}/submissions", contest_id)))
+            .expect("Submissions URL should be valid");
+
+        url.query_pairs_mut()
+            .extend_pairs(filter.query_pairs())
+            .append_pair("lang", "ja")
+            .append_pair("page", &page.to_string());
+
+        url.into()
+    }
+}
+
+/// Turns a page fetcher into a stream of items, starting from page 1.
+/// An empty page, `EmptyContents` or `NotFound` marks the end of the listing.
+fn paginate<T, F, Fut>(fetch_page: F) -> impl Stream<Item = Result<T, AtCoderClientError>>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, AtCoderClientError>>,
+{
+    stream::unfold((fetch_page, Some(1)), |(mut fetch_page, page)| async move {
+        let page = page?;
+        match fetch_page(page).await {
+            Ok(items) if items.is_empty() => None,
+            Ok(items) => Some((Ok(items), (fetch_page, Some(page + 1)))),
+            Err(e) if e.is_empty_content() => None,
+            Err(e) => Some((Err(e), (fetch_page, None))),
+        }
+    })
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atcoder_ids::{ProblemId, UserId};
+    use futures::StreamExt;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn unverified_client() -> AtCoderClient {
+        AtCoderClient::builder()
+            .skip_session_verification()
+            .build()
+            .await
+            .expect("client should be built")
+    }
+
+    #[tokio::test]
+    async fn contest_submissions_url_without_filter_has_only_paging_params() {
+        let client = unverified_client().await;
+        let url =
+            client.contest_submissions_url("adt_all_20250522_3", 2, &SubmissionFilter::default());
+
+        assert_eq!(
+            url,
+            "https://atcoder.jp/contests/adt_all_20250522_3/submissions?lang=ja&page=2"
+        );
     }
 
-    /// Constructs the URL for the ADT contest archive.
-    fn adt_archive_url(page: u32) -> String {
-        format!(
-            "{}/contests/archive?category=60&lang=ja&page={}",
-            ATCODER_BASE_URL, page
-        )
+    #[tokio::test]
+    async fn contest_submissions_url_with_filter_encodes_query_params() {
+        let client = unverified_client().await;
+        let filter = SubmissionFilter {
+            user: Some(UserId::parse("test1").unwrap()),
+            task: Some(ProblemId::parse("abc369_e").unwrap()),
+            language: Some("C++".to_string()),
+            ..SubmissionFilter::accepted()
+        };
+        let url = client.contest_submissions_url("adt_all_20250522_3", 1, &filter);
+
+        assert_eq!(
+            url,
+            "https://atcoder.jp/contests/adt_all_20250522_3/submissions\
+             ?f.Task=abc369_e&f.LanguageName=C%2B%2B&f.Status=AC&f.User=test1&lang=ja&page=1"
+        );
     }
 
-    /// Constructs the URL for a contest's submissions page.
-    fn contest_submissions_url(contest_id: &str, page: u32) -> String {
-        format!(
-            "{}/contests/{}/submissions?lang=ja&page={}",
-            ATCODER_BASE_URL, contest_id, page
-        )
+    #[tokio::test]
+    async fn contest_archive_url_encodes_query_params() {
+        let client = unverified_client().await;
+
+        assert_eq!(
+            client.contest_archive_url(&ContestArchiveQuery::adt(), 2),
+            "https://atcoder.jp/contests/archive?category=60&lang=ja&page=2"
+        );
+
+        let query = ContestArchiveQuery {
+            rated_type: Some(1),
+            keyword: Some("Daily Training".to_string()),
+            language: Some("en".to_string()),
+            ..ContestArchiveQuery::category(61)
+        };
+        assert_eq!(
+            client.contest_archive_url(&query, 1),
+            "https://atcoder.jp/contests/archive\
+             ?category=61&ratedType=1&keyword=Daily+Training&lang=en&page=1"
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_with_too_many_requests_returns_retry_after() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/contests/archive"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "120"))
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .build()
+            .await
+            .expect("client should be built");
+        let result = client.fetch_adt_contests(1).await;
+
+        let error = result.expect_err("fetch should fail");
+        assert!(error.is_retryable());
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(120)));
+    }
+
+    #[tokio::test]
+    async fn fetch_in_replay_mode_serves_recorded_pages_offline() {
+        let server = MockServer::start().await;
+        let dir = tempfile::tempdir().unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/contests/archive"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(include_str!("../test_resources/contests_page.txt")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let recorder = AtCoderClient::builder()
+            .base_url(server.uri())
+            .record_fixtures(dir.path())
+            .build()
+            .await
+            .expect("client should be built");
+        let recorded = recorder.fetch_adt_contests(1).await.unwrap();
+        let not_found = recorder
+            .fetch_contest_tasks(&ContestId::parse("unknown").unwrap())
+            .await;
+        assert!(matches!(not_found, Err(AtCoderClientError::NotFound)));
+
+        // The replaying client points at an unreachable host to prove it stays offline
+        let replayer = AtCoderClient::builder()
+            .base_url("http://127.0.0.1:9")
+            .replay_fixtures(dir.path())
+            .build()
+            .await
+            .expect("client should be built");
+        let replayed = replayer.fetch_adt_contests(1).await.unwrap();
+
+        assert_eq!(replayed, recorded);
+        assert!(matches!(
+            replayer
+                .fetch_contest_tasks(&ContestId::parse("unknown").unwrap())
+                .await,
+            Err(AtCoderClientError::NotFound)
+        ));
+        assert!(matches!(
+            replayer.fetch_adt_contests(2).await,
+            Err(AtCoderClientError::FixtureNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn submissions_stream_yields_items_until_not_found() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/contests/adt_all_20250522_3/submissions"))
+            .and(query_param("page", "1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(include_str!("../test_resources/submissions_page.txt")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/contests/adt_all_20250522_3/submissions"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .build()
+            .await
+            .expect("client should be built");
+        let submissions = client
+            .submissions_stream(
+                &ContestId::parse("adt_all_20250522_3").unwrap(),
+                &SubmissionFilter::default(),
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("stream should succeed");
+
+        assert_eq!(submissions.len(), 20);
+        assert_eq!(submissions[0].id, SubmissionId::new(66203973));
+    }
+
+    #[tokio::test]
+    async fn adt_contests_stream_fetches_pages_lazily() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/contests/archive"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(include_str!("../test_resources/contests_page.txt")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .build()
+            .await
+            .expect("client should be built");
+        let contests = client
+            .adt_contests_stream()
+            .take(3)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("stream should succeed");
+
+        assert_eq!(contests.len(), 3);
+        assert_eq!(contests[0].id, "adt_all_20250522_3");
+    }
+
+    #[tokio::test]
+    async fn stream_yields_error_and_ends() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/contests/archive"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .build()
+            .await
+            .expect("client should be built");
+        let results = client.adt_contests_stream().collect::<Vec<_>>().await;
+
+        assert_eq!(results.len(), 1);
+        assert!(matches!(
+            results[0],
+            Err(AtCoderClientError::ServerError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn fetch_maintenance_or_challenge_page_returns_distinct_errors() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/contests/archive"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(include_str!("../test_resources/maintenance_page.txt")),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/contests/adt_all_20250522_3/submissions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(include_str!("../test_resources/challenge_page.txt")),
+            )
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .build()
+            .await
+            .expect("client should be built");
+
+        let maintenance = client.fetch_adt_contests(1).await;
+        assert!(matches!(maintenance, Err(AtCoderClientError::Maintenance)));
+
+        let challenge = client
+            .fetch_submissions(
+                &ContestId::parse("adt_all_20250522_3").unwrap(),
+                1,
+                &SubmissionFilter::default(),
+            )
+            .await;
+        assert!(matches!(challenge, Err(AtCoderClientError::Challenge)));
+
+        // Streams must fail instead of ending as if the listing were exhausted
+        let results = client.adt_contests_stream().collect::<Vec<_>>().await;
+        assert!(matches!(
+            results.as_slice(),
+            [Err(AtCoderClientError::Maintenance)]
+        ));
     }
 }
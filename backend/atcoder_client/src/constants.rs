@@ -1,2 +1,9 @@
 pub const ATCODER_BASE_URL: &str = "https://atcoder.jp";
 pub const TEST_CONTEST_ID: &str = "abc388";
+pub const ADT_CONTEST_CATEGORY: u32 = 60;
+pub const REVEL_SESSION_COOKIE: &str = "REVEL_SESSION";
+pub const DEFAULT_REQUESTS_PER_SECOND: f64 = 3.0;
+pub const DEFAULT_RATE_LIMIT_BURST: u32 = 1;
+pub const DEFAULT_RETRY_MAX_RETRIES: u32 = 3;
+pub const DEFAULT_RETRY_BASE_DELAY_MILLIS: u64 = 1_000;
+pub const DEFAULT_RETRY_MAX_DELAY_MILLIS: u64 = 60_000;
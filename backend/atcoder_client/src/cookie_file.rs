@@ -0,0 +1,130 @@
+use crate::error::AtCoderClientError;
+use cookie_store::CookieStore;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufReader, BufWriter, Write},
+    path::PathBuf,
+};
+
+/// JSON file the cookie jar is persisted to between runs.
+/// Only unexpired persistent cookies (those with `Expires` or `Max-Age`) are saved.
+#[derive(Clone, Debug)]
+pub(crate) struct CookieFile {
+    path: PathBuf,
+}
+
+impl CookieFile {
+    pub(crate) fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Loads the saved cookies, skipping expired ones.
+    /// Returns an empty store if the file does not exist yet.
+    pub(crate) fn load(&self) -> Result<CookieStore, AtCoderClientError> {
+        if !self.path.exists() {
+            return Ok(CookieStore::default());
+        }
+
+        let file = File::open(&self.path).map_err(|e| self.error(e))?;
+        cookie_store::serde::json::load(BufReader::new(file)).map_err(|e| self.error(e))
+    }
+
+    /// Saves the cookies, replacing the file atomically so an interrupted run cannot corrupt it.
+    pub(crate) fn save(&self, store: &CookieStore) -> Result<(), AtCoderClientError> {
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|e| self.error(e))?;
+        }
+
+        let tmp_path = self.path.with_extension("tmp");
+        // The file holds the session secret, so only the owner may read it. A leftover temp
+        // file is removed first, since the mode only applies to newly created files.
+        if let Err(e) = fs::remove_file(&tmp_path)
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            return Err(self.error(e));
+        }
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut writer = BufWriter::new(options.open(&tmp_path).map_err(|e| self.error(e))?);
+        cookie_store::serde::json::save(store, &mut writer).map_err(|e| self.error(e))?;
+        writer.flush().map_err(|e| self.error(e))?;
+        drop(writer);
+
+        fs::rename(&tmp_path, &self.path).map_err(|e| self.error(e))
+    }
+
+    fn error(&self, e: impl std::fmt::Display) -> AtCoderClientError {
+        AtCoderClientError::CookieFileError(format!("{}: {}", self.path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Url;
+
+    #[test]
+    fn save_and_load_round_trip_keeps_only_persistent_cookies() {
+        let dir = tempfile::tempdir().unwrap();
+        let cookie_file = CookieFile::new(dir.path().join("nested").join("cookies.json"));
+        let url = Url::parse("https://atcoder.jp/").unwrap();
+
+        let mut store = CookieStore::default();
+        store
+            .parse(
+                "REVEL_SESSION=persisted; Path=/; Expires=Wed, 01 Jan 2100 00:00:00 GMT",
+                &url,
+            )
+            .unwrap();
+        store.parse("language=ja; Path=/", &url).unwrap();
+        cookie_file.save(&store).unwrap();
+
+        let loaded = cookie_file.load().unwrap();
+        let values: Vec<_> = loaded.get_request_values(&url).collect();
+        assert_eq!(values, vec![("REVEL_SESSION", "persisted")]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_creates_file_readable_only_by_owner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cookies.json");
+        // A leftover temp file with loose permissions must not be reused
+        fs::write(path.with_extension("tmp"), "").unwrap();
+        fs::set_permissions(
+            path.with_extension("tmp"),
+            fs::Permissions::from_mode(0o644),
+        )
+        .unwrap();
+
+        CookieFile::new(&path)
+            .save(&CookieStore::default())
+            .unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn load_returns_empty_store_when_file_is_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let cookie_file = CookieFile::new(dir.path().join("cookies.json"));
+
+        let store = cookie_file.load().unwrap();
+        assert_eq!(store.iter_any().count(), 0);
+    }
+
+    #[test]
+    fn load_reports_corrupted_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cookies.json");
+        fs::write(&path, "not json").unwrap();
+
+        let err = CookieFile::new(&path).load().unwrap_err();
+        assert!(matches!(err, AtCoderClientError::CookieFileError(_)));
+    }
+}
@@ -1,3 +1,5 @@
+use std::{fmt, time::Duration};
+
 /// Errors that can occur while using `AtCoderClient`.
 #[derive(Debug, thiserror::Error)]
 pub enum AtCoderClientError {
@@ -5,14 +7,30 @@ pub enum AtCoderClientError {
     #[error("Reqwest client error: {0}")]
     ReqwestError(#[from] reqwest::Error),
 
+    /// The configured URL could not be parsed.
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+
+    /// The configured rate limit is not a positive, finite rate with a non-zero burst.
+    #[error("Invalid rate limit: {0}")]
+    InvalidRateLimit(String),
+
     /// The provided REVEL_SESSION is invalid or has expired (HTTP 401 or redirect).
     #[error("Session is invalid or expired (HTTP 401 or redirect)")]
     InvalidSession,
 
+    /// The username or password was rejected by the login form.
+    #[error("Login failed: invalid username or password")]
+    InvalidCredentials,
+
     /// Access to the page is forbidden (HTTP 403).
     #[error("Access forbidden (HTTP 403)")]
     Forbidden,
 
+    /// Too many requests were sent (HTTP 429), optionally with the `Retry-After` delay.
+    #[error("Too many requests (HTTP 429)")]
+    TooManyRequests { retry_after: Option<Duration> },
+
     /// The requested page was not found (HTTP 404).
     #[error("Requested page does not exist (HTTP 404)")]
     NotFound,
@@ -29,17 +47,137 @@ pub enum AtCoderClientError {
     #[error("The page contains no meaningful contents")]
     EmptyContents,
 
+    /// AtCoder returned its maintenance notice instead of the requested page.
+    #[error("AtCoder is under maintenance")]
+    Maintenance,
+
+    /// An anti-bot challenge page was returned instead of the requested page.
+    #[error("Blocked by an anti-bot challenge page")]
+    Challenge,
+
+    /// Failed to read or write a recorded fixture.
+    #[error("Fixture I/O error: {0}")]
+    FixtureIoError(#[from] std::io::Error),
+
+    /// Failed to load or save the persisted cookie file.
+    #[error("Cookie file error: {0}")]
+    CookieFileError(String),
+
+    /// No fixture was recorded for the requested URL in replay mode.
+    #[error("No fixture recorded for {0}")]
+    FixtureNotFound(String),
+
+    /// A required column was not found in the table header.
+    #[error("Required column '{column}' not found in the {page} table header")]
+    MissingColumn {
+        page: &'static str,
+        column: &'static str,
+    },
+
     /// Failed to parse the expected HTML structure.
-    #[error("Failed to parse HTML content")]
-    HtmlParseError,
+    #[error("Failed to parse HTML content: {0}")]
+    HtmlParseError(Box<HtmlParseContext>),
+}
+
+/// Where and why parsing of a scraped page failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HtmlParseContext {
+    /// Kind of page being parsed (e.g. "submission list").
+    pub page: &'static str,
+    /// Index of the table row within `<tbody>`, if the failure is row-specific.
+    pub row: Option<usize>,
+    /// Name of the column being parsed, if any.
+    pub column: Option<&'static str>,
+    /// The offending text, truncated to a short snippet.
+    pub snippet: Option<String>,
+    /// Description of the expected content or format.
+    pub expected: &'static str,
+}
+
+impl HtmlParseContext {
+    /// Maximum number of characters kept in `snippet`.
+    const MAX_SNIPPET_CHARS: usize = 80;
+
+    pub fn new(page: &'static str, expected: &'static str) -> Self {
+        Self {
+            page,
+            row: None,
+            column: None,
+            snippet: None,
+            expected,
+        }
+    }
+
+    pub fn row(mut self, row: usize) -> Self {
+        self.row = Some(row);
+        self
+    }
+
+    pub fn column(mut self, column: &'static str) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Sets the offending text, collapsing whitespace and truncating it.
+    pub fn snippet(mut self, text: &str) -> Self {
+        let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut snippet = collapsed
+            .chars()
+            .take(Self::MAX_SNIPPET_CHARS)
+            .collect::<String>();
+        if collapsed.chars().count() > Self::MAX_SNIPPET_CHARS {
+            snippet.push('…');
+        }
+        self.snippet = Some(snippet);
+        self
+    }
+}
+
+impl fmt::Display for HtmlParseContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "page={}", self.page)?;
+        if let Some(row) = self.row {
+            write!(f, " row={}", row)?;
+        }
+        if let Some(column) = self.column {
+            write!(f, " column={}", column)?;
+        }
+        write!(f, " expected={}", self.expected)?;
+        if let Some(snippet) = &self.snippet {
+            write!(f, " got={:?}", snippet)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<HtmlParseContext> for AtCoderClientError {
+    fn from(context: HtmlParseContext) -> Self {
+        Self::HtmlParseError(Box::new(context))
+    }
 }
 
 impl AtCoderClientError {
+    /// Check if the page exists but has no contents, i.e. the end of a paginated listing.
+    /// Maintenance and challenge pages are never treated as empty.
     pub fn is_empty_content(&self) -> bool {
         matches!(self, Self::NotFound | Self::EmptyContents)
     }
 
+    /// Check if the request may succeed when retried later.
+    /// Covers rate limiting (403, 429), server errors (5xx) and transient network errors.
     pub fn is_retryable(&self) -> bool {
-        matches!(self, Self::Forbidden)
+        match self {
+            Self::Forbidden | Self::TooManyRequests { .. } | Self::ServerError(_) => true,
+            Self::ReqwestError(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// Returns the delay requested by the server via the `Retry-After` header, if any.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::TooManyRequests { retry_after } => *retry_after,
+            _ => None,
+        }
     }
 }
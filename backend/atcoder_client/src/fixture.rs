@@ -0,0 +1,182 @@
+use crate::error::AtCoderClientError;
+use reqwest::{StatusCode, Url};
+use std::{fs, path::PathBuf};
+
+/// Maximum length of the human-readable part of a fixture file name.
+const MAX_READABLE_KEY_LEN: usize = 100;
+
+/// A fetched page stored as a fixture: either its HTML or the non-successful status.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Fixture {
+    Page(String),
+    Status(StatusCode),
+}
+
+/// Whether fetched pages are saved to or served from the fixture directory.
+#[derive(Clone, Debug)]
+enum FixtureMode {
+    Record,
+    Replay,
+}
+
+/// Directory of HTML fixtures keyed by URL path and query (the host is ignored).
+/// Pages are stored as `{key}.html`, non-successful responses as `{key}.status`.
+#[derive(Clone, Debug)]
+pub(crate) struct FixtureStore {
+    mode: FixtureMode,
+    dir: PathBuf,
+}
+
+impl FixtureStore {
+    /// Creates a store that saves every fetched page under `dir`.
+    pub(crate) fn record(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: FixtureMode::Record,
+            dir: dir.into(),
+        }
+    }
+
+    /// Creates a store that serves pages from `dir` instead of the network.
+    pub(crate) fn replay(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: FixtureMode::Replay,
+            dir: dir.into(),
+        }
+    }
+
+    pub(crate) fn is_replay(&self) -> bool {
+        matches!(self.mode, FixtureMode::Replay)
+    }
+
+    /// Loads the fixture recorded for the given URL.
+    pub(crate) fn load(&self, url: &Url) -> Result<Fixture, AtCoderClientError> {
+        let key = Self::key(url);
+
+        let html_path = self.dir.join(format!("{}.html", key));
+        if html_path.exists() {
+            return Ok(Fixture::Page(fs::read_to_string(html_path)?));
+        }
+
+        let status_path = self.dir.join(format!("{}.status", key));
+        if status_path.exists() {
+            let status = fs::read_to_string(status_path)?
+                .trim()
+                .parse::<u16>()
+                .ok()
+                .and_then(|code| StatusCode::from_u16(code).ok())
+                .ok_or_else(|| AtCoderClientError::FixtureNotFound(url.to_string()))?;
+            return Ok(Fixture::Status(status));
+        }
+
+        Err(AtCoderClientError::FixtureNotFound(url.to_string()))
+    }
+
+    /// Saves the fixture for the given URL, replacing any previous recording.
+    pub(crate) fn save(&self, url: &Url, fixture: &Fixture) -> Result<(), AtCoderClientError> {
+        let key = Self::key(url);
+        fs::create_dir_all(&self.dir)?;
+
+        let html_path = self.dir.join(format!("{}.html", key));
+        let status_path = self.dir.join(format!("{}.status", key));
+
+        match fixture {
+            Fixture::Page(html) => {
+                fs::write(html_path, html)?;
+                if status_path.exists() {
+                    fs::remove_file(status_path)?;
+                }
+            }
+            Fixture::Status(status) => {
+                fs::write(status_path, status.as_str())?;
+                if html_path.exists() {
+                    fs::remove_file(html_path)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds a file-system safe key from the URL path and query.
+    /// A stable hash of the full path keeps sanitized keys from colliding.
+    fn key(url: &Url) -> String {
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+
+        let readable = path
+            .trim_start_matches('/')
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .take(MAX_READABLE_KEY_LEN)
+            .collect::<String>();
+
+        format!("{}-{:016x}", readable, fnv1a_64(path.as_bytes()))
+    }
+}
+
+/// 64-bit FNV-1a hash, used because it is stable across Rust versions and platforms.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_ignores_host_and_keeps_path_readable() {
+        let prod =
+            Url::parse("https://atcoder.jp/contests/abc388/submissions?lang=ja&page=1").unwrap();
+        let local =
+            Url::parse("http://127.0.0.1:8080/contests/abc388/submissions?lang=ja&page=1").unwrap();
+
+        let key = FixtureStore::key(&prod);
+        assert_eq!(key, FixtureStore::key(&local));
+        assert!(key.starts_with("contests_abc388_submissions_lang_ja_page_1-"));
+    }
+
+    #[test]
+    fn key_differs_for_paths_that_sanitize_alike() {
+        let a = Url::parse("https://atcoder.jp/contests/a_b").unwrap();
+        let b = Url::parse("https://atcoder.jp/contests/a/b").unwrap();
+
+        assert_ne!(FixtureStore::key(&a), FixtureStore::key(&b));
+    }
+
+    #[test]
+    fn save_and_load_round_trips_pages_and_statuses() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = FixtureStore::record(dir.path());
+        let replayer = FixtureStore::replay(dir.path());
+
+        let page_url = Url::parse("https://atcoder.jp/contests/archive?page=1").unwrap();
+        let missing_url = Url::parse("https://atcoder.jp/contests/archive?page=2").unwrap();
+        let page = Fixture::Page("<html></html>".to_string());
+        let status = Fixture::Status(StatusCode::NOT_FOUND);
+
+        recorder.save(&page_url, &page).unwrap();
+        recorder.save(&missing_url, &status).unwrap();
+
+        assert_eq!(replayer.load(&page_url).unwrap(), page);
+        assert_eq!(replayer.load(&missing_url).unwrap(), status);
+
+        let unknown_url = Url::parse("https://atcoder.jp/contests/archive?page=3").unwrap();
+        assert!(matches!(
+            replayer.load(&unknown_url),
+            Err(AtCoderClientError::FixtureNotFound(_))
+        ));
+    }
+}
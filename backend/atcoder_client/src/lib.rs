@@ -1,9 +1,20 @@
+mod builder;
 mod client;
 mod constants;
+mod cookie_file;
 mod error;
+mod fixture;
 mod models;
+mod rate_limiter;
+mod retry;
 mod scraper;
 
+pub use builder::AtCoderClientBuilder;
 pub use client::AtCoderClient;
-pub use error::AtCoderClientError;
-pub use models::{Contest, Submission};
+pub use error::{AtCoderClientError, HtmlParseContext};
+pub use models::{
+    Contest, ContestArchiveQuery, ContestSchedule, ContestTask, JudgeStatus, ScrapedPage,
+    Submission, SubmissionDetail, SubmissionFilter, TestCaseResult,
+};
+pub use rate_limiter::RateLimitMetrics;
+pub use retry::RetryPolicy;
@@ -1,27 +1,315 @@
+use crate::constants::ADT_CONTEST_CATEGORY;
+use crate::error::HtmlParseContext;
+use atcoder_ids::{ContestId, ProblemId, SubmissionId, UserId};
+use std::fmt;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Contest {
-    pub id: String,
+    pub id: ContestId,
     pub start_epoch_second: u64,
     pub duration_second: u64,
     pub title: String,
     pub rate_change: String,
 }
 
+/// Contests listed on the contest list page (`/contests/`) that have not ended yet.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContestSchedule {
+    /// Contests currently running.
+    pub active: Vec<Contest>,
+    /// Contests scheduled to start, soonest first.
+    pub upcoming: Vec<Contest>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContestTask {
+    pub label: String,
+    pub problem_id: ProblemId,
+    pub title: String,
+    pub time_limit_millis: u64,
+    pub memory_limit_mb: u64,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Submission {
-    pub id: u64,
+    pub id: SubmissionId,
     pub epoch_second: u64,
-    pub problem_id: String,
-    pub contest_id: String,
-    pub user_id: String,
+    pub problem_id: ProblemId,
+    pub contest_id: ContestId,
+    pub user_id: UserId,
     pub language: String,
     pub point: f64,
     pub length: u64,
-    pub result: String,
+    pub result: JudgeStatus,
     pub execution_time: Option<u64>,
+    pub memory_kb: Option<u64>,
+}
+impl Contest {
+    /// Check whether this is an AtCoder Daily Training contest.
+    pub fn is_adt(&self) -> bool {
+        self.id.is_adt()
+    }
 }
+
 impl Submission {
     pub fn is_accepted(&self) -> bool {
-        self.result == "AC"
+        self.result == JudgeStatus::Accepted
+    }
+}
+
+/// Judge status of a submission as shown in the status column of the submissions page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JudgeStatus {
+    /// AC
+    Accepted,
+    /// WA
+    WrongAnswer,
+    /// TLE
+    TimeLimitExceeded,
+    /// MLE
+    MemoryLimitExceeded,
+    /// RE
+    RuntimeError,
+    /// CE
+    CompilationError,
+    /// OLE
+    OutputLimitExceeded,
+    /// IE
+    InternalError,
+    /// WJ: waiting for the judge to start.
+    WaitingForJudge,
+    /// WR: waiting for rejudge.
+    WaitingForRejudge,
+    /// Judging in progress, e.g. "3/20 WJ" or "3/20 AC" (the verdict so far is not kept).
+    Judging { judged: u32, total: u32 },
+    /// Any status not known to this client, kept verbatim.
+    Other(String),
+}
+
+impl JudgeStatus {
+    /// Parses the text of the status column, e.g. "AC" or "3/20 WJ".
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+
+        // The progress counts come first, optionally followed by the verdict so far
+        let progress = text.split_whitespace().next().unwrap_or_default();
+        if let Some((judged, total)) = progress.split_once('/')
+            && let (Ok(judged), Ok(total)) = (judged.parse(), total.parse())
+        {
+            return Self::Judging { judged, total };
+        }
+
+        match text {
+            "AC" => Self::Accepted,
+            "WA" => Self::WrongAnswer,
+            "TLE" => Self::TimeLimitExceeded,
+            "MLE" => Self::MemoryLimitExceeded,
+            "RE" => Self::RuntimeError,
+            "CE" => Self::CompilationError,
+            "OLE" => Self::OutputLimitExceeded,
+            "IE" => Self::InternalError,
+            "WJ" => Self::WaitingForJudge,
+            "WR" => Self::WaitingForRejudge,
+            other => Self::Other(other.to_owned()),
+        }
+    }
+
+    /// Returns `false` while the submission is waiting for or under judging.
+    pub fn is_final(&self) -> bool {
+        !matches!(
+            self,
+            Self::WaitingForJudge | Self::WaitingForRejudge | Self::Judging { .. }
+        )
+    }
+}
+
+impl fmt::Display for JudgeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            Self::Accepted => "AC",
+            Self::WrongAnswer => "WA",
+            Self::TimeLimitExceeded => "TLE",
+            Self::MemoryLimitExceeded => "MLE",
+            Self::RuntimeError => "RE",
+            Self::CompilationError => "CE",
+            Self::OutputLimitExceeded => "OLE",
+            Self::InternalError => "IE",
+            Self::WaitingForJudge => "WJ",
+            Self::WaitingForRejudge => "WR",
+            Self::Judging { judged, total } => return write!(f, "{}/{}", judged, total),
+            Self::Other(text) => text,
+        };
+        f.write_str(code)
+    }
+}
+
+/// Details of a single submission from its detail page.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubmissionDetail {
+    pub id: SubmissionId,
+    pub contest_id: ContestId,
+    pub problem_id: ProblemId,
+    pub user_id: UserId,
+    pub language: String,
+    pub result: JudgeStatus,
+    pub execution_time: Option<u64>,
+    pub memory_kb: Option<u64>,
+    pub source_code: String,
+    /// Per-test-case verdicts, empty if the submission was not judged (e.g. CE or WJ).
+    pub test_cases: Vec<TestCaseResult>,
+    pub submitted_epoch_second: u64,
+    /// When judging finished, if shown on the page.
+    pub judged_epoch_second: Option<u64>,
+}
+
+/// Verdict of a single test case of a submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestCaseResult {
+    pub name: String,
+    pub result: JudgeStatus,
+    pub execution_time: Option<u64>,
+    pub memory_kb: Option<u64>,
+}
+
+/// Filter conditions for the submissions page.
+/// Each field maps to AtCoder's `f.*` query parameter; `None` leaves it unfiltered.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubmissionFilter {
+    /// Judge status such as "AC" or "WA" (`f.Status`).
+    pub status: Option<String>,
+    /// User ID of the submitter (`f.User`).
+    pub user: Option<UserId>,
+    /// Task (problem) ID such as "abc369_e" (`f.Task`).
+    pub task: Option<ProblemId>,
+    /// Language name such as "C++" (`f.LanguageName`).
+    pub language: Option<String>,
+}
+impl SubmissionFilter {
+    /// Creates a filter that only matches accepted submissions.
+    pub fn accepted() -> Self {
+        Self {
+            status: Some("AC".to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the non-empty filter conditions as query parameter pairs.
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("f.Task", self.task.as_ref().map(ProblemId::as_str)),
+            ("f.LanguageName", self.language.as_deref()),
+            ("f.Status", self.status.as_deref()),
+            ("f.User", self.user.as_ref().map(UserId::as_str)),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|v| (key, v)))
+        .collect()
+    }
+}
+
+/// Search conditions for the contest archive page.
+/// Each field maps to the archive's query parameter; `None` leaves it unfiltered.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContestArchiveQuery {
+    /// Contest category such as 60 for AtCoder Daily Training (`category`).
+    pub category: Option<u32>,
+    /// Rated type as selected in the archive filter form, e.g. 1 for ABC class (`ratedType`).
+    pub rated_type: Option<u32>,
+    /// Keyword matched against contest titles (`keyword`).
+    pub keyword: Option<String>,
+    /// Page language such as "ja" or "en" (`lang`), defaulting to "ja".
+    pub language: Option<String>,
+}
+impl ContestArchiveQuery {
+    /// Creates a query for the contests of the given category.
+    pub fn category(category: u32) -> Self {
+        Self {
+            category: Some(category),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a query for AtCoder Daily Training contests.
+    pub fn adt() -> Self {
+        Self::category(ADT_CONTEST_CATEGORY)
+    }
+
+    /// Returns the query parameter pairs, always including the page language.
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(category) = self.category {
+            pairs.push(("category", category.to_string()));
+        }
+        if let Some(rated_type) = self.rated_type {
+            pairs.push(("ratedType", rated_type.to_string()));
+        }
+        if let Some(keyword) = &self.keyword {
+            pairs.push(("keyword", keyword.clone()));
+        }
+        pairs.push(("lang", self.language.as_deref().unwrap_or("ja").to_owned()));
+        pairs
+    }
+}
+
+/// Rows scraped from a listing page, with the position of the page in the listing.
+/// In lenient mode malformed rows are skipped and reported in `rejected` instead of failing the whole page.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScrapedPage<T> {
+    pub items: Vec<T>,
+    /// Parse errors of the skipped rows, each with its row index and reason.
+    pub rejected: Vec<HtmlParseContext>,
+    /// The 1-based page number according to the pagination widget.
+    pub current_page: u32,
+    /// The number of pages in the listing, or `None` if the page only links to its neighbours.
+    pub total_pages: Option<u32>,
+}
+
+impl<T> Default for ScrapedPage<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            rejected: Vec::new(),
+            current_page: 1,
+            total_pages: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn judge_status_parse_known_codes() {
+        assert_eq!(JudgeStatus::parse("AC"), JudgeStatus::Accepted);
+        assert_eq!(JudgeStatus::parse(" TLE "), JudgeStatus::TimeLimitExceeded);
+        assert_eq!(JudgeStatus::parse("WR"), JudgeStatus::WaitingForRejudge);
+        assert_eq!(
+            JudgeStatus::parse("QLE"),
+            JudgeStatus::Other("QLE".to_string())
+        );
+    }
+
+    #[test]
+    fn judge_status_parse_in_progress() {
+        let status = JudgeStatus::parse("3/20 WJ");
+
+        assert_eq!(
+            status,
+            JudgeStatus::Judging {
+                judged: 3,
+                total: 20
+            }
+        );
+        assert_eq!(JudgeStatus::parse("3/20"), status);
+        assert!(!status.is_final());
+        assert_eq!(status.to_string(), "3/20");
+    }
+
+    #[test]
+    fn judge_status_is_final() {
+        assert!(JudgeStatus::Accepted.is_final());
+        assert!(JudgeStatus::CompilationError.is_final());
+        assert!(!JudgeStatus::WaitingForJudge.is_final());
     }
 }
@@ -0,0 +1,155 @@
+use std::sync::Mutex;
+use tokio::time::{Duration, Instant, sleep};
+
+/// Snapshot of the time requests spent waiting on the rate limiter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RateLimitMetrics {
+    /// Number of requests that passed through the rate limiter.
+    pub total_requests: u64,
+    /// Number of requests that had to wait for a token.
+    pub throttled_requests: u64,
+    /// Total time spent waiting across all requests.
+    pub total_wait: Duration,
+    /// Longest single wait.
+    pub max_wait: Duration,
+}
+
+/// Token-bucket rate limiter shared by all clones of a client.
+/// Tokens are reserved in arrival order, so concurrent callers are spaced out evenly.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    /// Available tokens; negative while callers are waiting on reserved tokens.
+    tokens: f64,
+    last_refill: Instant,
+    metrics: RateLimitMetrics,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter with a full bucket.
+    /// Panics unless `requests_per_second` is positive and finite and `burst` is non-zero;
+    /// the builder validates both before getting here.
+    pub(crate) fn new(requests_per_second: f64, burst: u32) -> Self {
+        assert!(
+            requests_per_second.is_finite() && requests_per_second > 0.0,
+            "requests_per_second must be positive and finite, got {requests_per_second}"
+        );
+        assert!(burst > 0, "burst must be at least 1");
+        let burst = f64::from(burst);
+        Self {
+            requests_per_second,
+            burst,
+            state: Mutex::new(State {
+                tokens: burst,
+                last_refill: Instant::now(),
+                metrics: RateLimitMetrics::default(),
+            }),
+        }
+    }
+
+    /// Waits until a token is available and consumes it.
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut state = self
+                .state
+                .lock()
+                .expect("Rate limiter lock should not be poisoned");
+
+            let now = Instant::now();
+            let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+            state.tokens = (state.tokens + elapsed * self.requests_per_second).min(self.burst);
+            state.last_refill = now;
+
+            // Reserve a token; a negative balance is the backlog of waiting callers
+            state.tokens -= 1.0;
+            let wait = if state.tokens < 0.0 {
+                Duration::from_secs_f64(-state.tokens / self.requests_per_second)
+            } else {
+                Duration::ZERO
+            };
+
+            state.metrics.total_requests += 1;
+            if !wait.is_zero() {
+                state.metrics.throttled_requests += 1;
+                state.metrics.total_wait += wait;
+                state.metrics.max_wait = state.metrics.max_wait.max(wait);
+            }
+
+            wait
+        };
+
+        if !wait.is_zero() {
+            sleep(wait).await;
+        }
+    }
+
+    /// Returns a snapshot of the wait metrics.
+    pub(crate) fn metrics(&self) -> RateLimitMetrics {
+        self.state
+            .lock()
+            .expect("Rate limiter lock should not be poisoned")
+            .metrics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_within_burst_does_not_wait() {
+        let limiter = RateLimiter::new(1.0, 3);
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        assert_eq!(limiter.metrics().throttled_requests, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_beyond_burst_waits_and_records_metrics() {
+        let limiter = RateLimiter::new(2.0, 1);
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+
+        let metrics = limiter.metrics();
+        assert_eq!(metrics.total_requests, 3);
+        assert_eq!(metrics.throttled_requests, 2);
+        assert_eq!(metrics.total_wait, Duration::from_secs(1));
+        assert_eq!(metrics.max_wait, Duration::from_millis(500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_from_concurrent_tasks_is_spaced_out() {
+        let limiter = Arc::new(RateLimiter::new(4.0, 1));
+        let start = Instant::now();
+
+        let handles = (0..4)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move { limiter.acquire().await })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(start.elapsed(), Duration::from_millis(750));
+        assert_eq!(limiter.metrics().total_wait, Duration::from_millis(1500));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::constants::{
+    DEFAULT_RETRY_BASE_DELAY_MILLIS, DEFAULT_RETRY_MAX_DELAY_MILLIS, DEFAULT_RETRY_MAX_RETRIES,
+};
+use crate::error::AtCoderClientError;
+use std::future::Future;
+use tokio::time::{Duration, sleep};
+
+/// Retry policy with exponential backoff and jitter for retryable `AtCoderClientError`s.
+/// A `Retry-After` hint from a 429 response takes precedence over the computed backoff,
+/// unless it is longer than `max_delay`, in which case the request is not retried.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt.
+    pub max_retries: u32,
+    /// Backoff before the first retry; doubled on each subsequent retry.
+    pub base_delay: Duration,
+    /// Upper bound of the computed backoff and of an honored `Retry-After`.
+    pub max_delay: Duration,
+    /// Whether to randomize the backoff to spread out concurrent retries.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_RETRY_MAX_RETRIES,
+            base_delay: Duration::from_millis(DEFAULT_RETRY_BASE_DELAY_MILLIS),
+            max_delay: Duration::from_millis(DEFAULT_RETRY_MAX_DELAY_MILLIS),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Returns how long to wait before retrying after the given failed attempt (0-based),
+    /// or `None` if the error is not retryable, the retry limit is reached or the server asks
+    /// to wait longer than `max_delay`.
+    pub fn next_delay(&self, attempt: u32, error: &AtCoderClientError) -> Option<Duration> {
+        if attempt >= self.max_retries || !error.is_retryable() {
+            return None;
+        }
+
+        if let Some(retry_after) = error.retry_after() {
+            return (retry_after <= self.max_delay).then_some(retry_after);
+        }
+
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+
+        if self.jitter {
+            // Equal jitter: keep half of the backoff and randomize the other half
+            let half = backoff / 2;
+            Some(half + half.mul_f64(fastrand::f64()))
+        } else {
+            Some(backoff)
+        }
+    }
+
+    /// Runs `operation`, retrying retryable errors according to this policy.
+    pub async fn retry<T, F, Fut>(&self, mut operation: F) -> Result<T, AtCoderClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, AtCoderClientError>>,
+    {
+        let mut attempt = 0;
+
+        loop {
+            let error = match operation().await {
+                Ok(value) => return Ok(value),
+                Err(e) => e,
+            };
+
+            let Some(delay) = self.next_delay(attempt, &error) else {
+                return Err(error);
+            };
+
+            attempt += 1;
+            log::warn!(
+                "Retrying request (attempt {}/{}) after {}ms: {}",
+                attempt,
+                self.max_retries,
+                delay.as_millis(),
+                error
+            );
+            sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn policy_without_jitter() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(3),
+            jitter: false,
+        }
+    }
+
+    #[test]
+    fn next_delay_grows_exponentially_up_to_max_delay() {
+        let policy = policy_without_jitter();
+        let error = AtCoderClientError::Forbidden;
+
+        assert_eq!(policy.next_delay(0, &error), Some(Duration::from_secs(1)));
+        assert_eq!(policy.next_delay(1, &error), Some(Duration::from_secs(2)));
+        assert_eq!(policy.next_delay(2, &error), Some(Duration::from_secs(3)));
+        assert_eq!(policy.next_delay(3, &error), None);
+    }
+
+    #[test]
+    fn next_delay_with_jitter_stays_within_backoff() {
+        let policy = RetryPolicy {
+            jitter: true,
+            ..policy_without_jitter()
+        };
+        let error = AtCoderClientError::ServerError(reqwest::StatusCode::BAD_GATEWAY);
+
+        for _ in 0..100 {
+            let delay = policy.next_delay(1, &error).unwrap();
+            assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+    }
+
+    #[test]
+    fn next_delay_honors_retry_after() {
+        let policy = RetryPolicy {
+            max_delay: Duration::from_secs(60),
+            ..policy_without_jitter()
+        };
+        let error = AtCoderClientError::TooManyRequests {
+            retry_after: Some(Duration::from_secs(30)),
+        };
+
+        assert_eq!(policy.next_delay(0, &error), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn next_delay_gives_up_when_retry_after_exceeds_max_delay() {
+        let policy = policy_without_jitter();
+        let error = AtCoderClientError::TooManyRequests {
+            retry_after: Some(Duration::from_secs(86_400)),
+        };
+
+        assert_eq!(policy.next_delay(0, &error), None);
+    }
+
+    #[test]
+    fn next_delay_with_non_retryable_error_returns_none() {
+        let policy = policy_without_jitter();
+
+        assert_eq!(policy.next_delay(0, &AtCoderClientError::NotFound), None);
+        assert_eq!(
+            policy.next_delay(0, &AtCoderClientError::InvalidSession),
+            None
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_succeeds_after_retryable_errors() {
+        let policy = policy_without_jitter();
+        let calls = Cell::new(0);
+
+        let result = policy
+            .retry(|| {
+                calls.set(calls.get() + 1);
+                let call = calls.get();
+                async move {
+                    if call < 3 {
+                        Err(AtCoderClientError::TooManyRequests { retry_after: None })
+                    } else {
+                        Ok(call)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_returns_last_error_when_retries_are_exhausted() {
+        let policy = policy_without_jitter();
+        let calls = Cell::new(0);
+
+        let result: Result<(), _> = policy
+            .retry(|| {
+                calls.set(calls.get() + 1);
+                async { Err(AtCoderClientError::Forbidden) }
+            })
+            .await;
+
+        assert!(matches!(result, Err(AtCoderClientError::Forbidden)));
+        assert_eq!(calls.get(), 4);
+    }
+}
@@ -1,5 +1,18 @@
+mod columns;
 mod contest;
+mod interstitial;
+mod login;
+mod pagination;
 mod submission;
+mod submission_detail;
+mod task;
 
 pub use contest::scrape as scrape_contest_page;
+pub use contest::scrape_page as scrape_contest_page_with_pagination;
+pub use contest::scrape_schedule as scrape_contest_schedule_page;
+pub use interstitial::detect as detect_interstitial_page;
+pub use login::scrape as scrape_login_page;
 pub use submission::scrape as scrape_submission_page;
+pub use submission::scrape_lenient as scrape_submission_page_lenient;
+pub use submission_detail::scrape as scrape_submission_detail_page;
+pub use task::scrape as scrape_task_page;
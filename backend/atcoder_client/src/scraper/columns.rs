@@ -0,0 +1,193 @@
+use crate::error::{AtCoderClientError, HtmlParseContext};
+use scraper::{ElementRef, Selector};
+use std::{collections::HashMap, str::FromStr};
+
+/// A table column identified by its header label in Japanese or English.
+pub struct Column {
+    pub name: &'static str,
+    pub labels: &'static [&'static str],
+    pub required: bool,
+}
+
+/// Maps column names to their positions, built from the `<thead>` labels of a table.
+pub struct ColumnMap {
+    page: &'static str,
+    indices: HashMap<&'static str, usize>,
+}
+
+/// The cells of a table row with the column position each cell starts at.
+/// Errors raised through a row carry the page kind and row index as context.
+pub struct Row<'a> {
+    page: &'static str,
+    index: usize,
+    tr: ElementRef<'a>,
+    cells: Vec<(usize, ElementRef<'a>)>,
+}
+
+impl ColumnMap {
+    /// Builds the column map from the header of `table`.
+    /// Exact label matches take precedence over prefix matches (e.g. "開始時刻" with a `<small>` suffix).
+    pub fn from_header(
+        table: ElementRef,
+        page: &'static str,
+        columns: &[Column],
+    ) -> Result<Self, AtCoderClientError> {
+        let th_selector = Selector::parse("thead th").unwrap();
+        let headers = table
+            .select(&th_selector)
+            .map(|th| normalize(&th.text().collect::<String>()))
+            .collect::<Vec<_>>();
+
+        let mut indices = HashMap::new();
+        for column in columns {
+            let index = headers
+                .iter()
+                .position(|h| column.labels.contains(&h.as_str()))
+                .or_else(|| {
+                    headers.iter().position(|h| {
+                        column
+                            .labels
+                            .iter()
+                            .any(|label| !label.is_empty() && h.starts_with(label))
+                    })
+                });
+
+            match index {
+                Some(index) => {
+                    indices.insert(column.name, index);
+                }
+                None if column.required => {
+                    return Err(AtCoderClientError::MissingColumn {
+                        page,
+                        column: column.name,
+                    });
+                }
+                None => {}
+            }
+        }
+
+        Ok(Self { page, indices })
+    }
+
+    /// Collects the `<td>` cells of the row at `index`, expanding `colspan` into column positions.
+    pub fn row<'a>(&self, index: usize, tr: ElementRef<'a>) -> Row<'a> {
+        let td_selector = Selector::parse("td").unwrap();
+
+        let mut position = 0;
+        let cells = tr
+            .select(&td_selector)
+            .map(|td| {
+                let start = position;
+                let span = td
+                    .value()
+                    .attr("colspan")
+                    .and_then(|s| s.trim().parse::<usize>().ok())
+                    .unwrap_or(1)
+                    .max(1);
+                position += span;
+                (start, td)
+            })
+            .collect();
+
+        Row {
+            page: self.page,
+            index,
+            tr,
+            cells,
+        }
+    }
+
+    /// Returns the cell of the given column, or `None` if the column is absent
+    /// or covered by a cell spanning from an earlier column (e.g. `colspan` on CE results).
+    pub fn optional_cell<'a>(&self, row: &Row<'a>, name: &'static str) -> Option<ElementRef<'a>> {
+        let index = *self.indices.get(name)?;
+        row.cells
+            .iter()
+            .find(|(start, _)| *start == index)
+            .map(|(_, cell)| *cell)
+    }
+
+    /// Returns the cell of the given column.
+    pub fn cell<'a>(
+        &self,
+        row: &Row<'a>,
+        name: &'static str,
+    ) -> Result<ElementRef<'a>, HtmlParseContext> {
+        self.optional_cell(row, name)
+            .ok_or_else(|| row.error("cell for this column", &row.text()).column(name))
+    }
+
+    /// Returns the first text node of the given column's cell.
+    pub fn text<'a>(&self, row: &Row<'a>, name: &'static str) -> Result<&'a str, HtmlParseContext> {
+        let cell = self.cell(row, name)?;
+        cell.text()
+            .next()
+            .ok_or_else(|| row.error("non-empty text", &cell.html()).column(name))
+    }
+
+    /// Returns the first link of the given column's cell.
+    pub fn link<'a>(
+        &self,
+        row: &Row<'a>,
+        name: &'static str,
+    ) -> Result<ElementRef<'a>, HtmlParseContext> {
+        let a_selector = Selector::parse("a").unwrap();
+        let cell = self.cell(row, name)?;
+        cell.select(&a_selector)
+            .next()
+            .ok_or_else(|| row.error("<a> link", &cell.html()).column(name))
+    }
+
+    /// Parses the last path segment of the first link's `href` in the given column's cell as an ID.
+    pub fn link_id<T: FromStr>(
+        &self,
+        row: &Row,
+        name: &'static str,
+    ) -> Result<T, HtmlParseContext> {
+        let a = self.link(row, name)?;
+        a.value()
+            .attr("href")
+            .and_then(|href| href.rsplit('/').next())
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(|| {
+                row.error("href ending with a valid ID", &a.html())
+                    .column(name)
+            })
+    }
+}
+
+impl<'a> Row<'a> {
+    /// Builds a parse error context for this row.
+    pub fn error(&self, expected: &'static str, snippet: &str) -> HtmlParseContext {
+        HtmlParseContext::new(self.page, expected)
+            .row(self.index)
+            .snippet(snippet)
+    }
+
+    /// Returns all links in the row.
+    pub fn links(&self) -> Vec<ElementRef<'a>> {
+        let a_selector = Selector::parse("a").unwrap();
+        self.tr.select(&a_selector).collect()
+    }
+
+    /// Returns the whole text of the row, used as an error snippet.
+    pub fn text(&self) -> String {
+        self.tr.text().collect()
+    }
+}
+
+/// Returns the parent `<table>` of a `<tbody>` element.
+pub fn parent_table<'a>(
+    tbody: ElementRef<'a>,
+    page: &'static str,
+) -> Result<ElementRef<'a>, AtCoderClientError> {
+    tbody
+        .parent()
+        .and_then(ElementRef::wrap)
+        .ok_or_else(|| HtmlParseContext::new(page, "<table> around <tbody>").into())
+}
+
+/// Trims and collapses whitespace in a header label.
+pub fn normalize(label: &str) -> String {
+    label.split_whitespace().collect::<Vec<_>>().join(" ")
+}
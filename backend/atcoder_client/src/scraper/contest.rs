@@ -1,84 +1,127 @@
 use crate::error::AtCoderClientError;
-use crate::models::Contest;
+use crate::models::{Contest, ContestSchedule, ScrapedPage};
+use crate::scraper::columns::{Column, ColumnMap, parent_table};
+use crate::scraper::pagination;
 use chrono::DateTime;
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
+
+const PAGE: &str = "contest archive";
+const SCHEDULE_PAGE: &str = "contest schedule";
+
+const START_TIME: &str = "start_time";
+const CONTEST_NAME: &str = "contest_name";
+const DURATION: &str = "duration";
+const RATED_RANGE: &str = "rated_range";
+
+const COLUMNS: &[Column] = &[
+    Column {
+        name: START_TIME,
+        labels: &["開始時刻", "Start Time"],
+        required: true,
+    },
+    Column {
+        name: CONTEST_NAME,
+        labels: &["コンテスト名", "Contest Name"],
+        required: true,
+    },
+    Column {
+        name: DURATION,
+        labels: &["時間", "Duration"],
+        required: true,
+    },
+    Column {
+        name: RATED_RANGE,
+        labels: &["Rated対象", "Rated Range"],
+        required: true,
+    },
+];
 
 /// Parses the HTML of the AtCoder contest archive page and returns a list of contests.
+/// Cells are located by the `<thead>` labels, so reordered or extra columns are tolerated.
 pub fn scrape(html: &str) -> Result<Vec<Contest>, AtCoderClientError> {
     let document = Html::parse_document(html);
+    scrape_rows(&document)
+}
+
+/// Parses the contest archive page like [`scrape`], along with the position of the page
+/// read from the pagination widget.
+pub fn scrape_page(html: &str) -> Result<ScrapedPage<Contest>, AtCoderClientError> {
+    let document = Html::parse_document(html);
+    let pagination = pagination::scrape(&document);
+
+    Ok(ScrapedPage {
+        items: scrape_rows(&document)?,
+        current_page: pagination.current_page,
+        total_pages: pagination.total_pages,
+        ..ScrapedPage::default()
+    })
+}
+
+/// Parses the HTML of the AtCoder contest list page (`/contests/`)
+/// and returns the contests currently running and those scheduled to start.
+/// A missing section means there are no such contests.
+pub fn scrape_schedule(html: &str) -> Result<ContestSchedule, AtCoderClientError> {
+    let document = Html::parse_document(html);
+
+    let section = |id: &str| -> Result<Vec<Contest>, AtCoderClientError> {
+        let tbody_selector = Selector::parse(&format!("#{} tbody", id)).unwrap();
+        match document.select(&tbody_selector).next() {
+            Some(tbody) => scrape_tbody(tbody, SCHEDULE_PAGE),
+            None => Ok(Vec::new()),
+        }
+    };
+
+    Ok(ContestSchedule {
+        active: section("contest-table-action")?,
+        upcoming: section("contest-table-upcoming")?,
+    })
+}
 
+fn scrape_rows(document: &Html) -> Result<Vec<Contest>, AtCoderClientError> {
     let tbody_selector = Selector::parse("tbody").unwrap();
     let tbody = document
         .select(&tbody_selector)
         .next()
         .ok_or(AtCoderClientError::EmptyContents)?;
 
+    scrape_tbody(tbody, PAGE)
+}
+
+/// Parses the rows of a contest table with the archive's columns.
+fn scrape_tbody(tbody: ElementRef, page: &'static str) -> Result<Vec<Contest>, AtCoderClientError> {
+    let table = parent_table(tbody, page)?;
+    let columns = ColumnMap::from_header(table, page, COLUMNS)?;
+
     let tr_selector = Selector::parse("tr").unwrap();
     tbody
         .select(&tr_selector)
-        .map(|tr| {
-            let td_selector = Selector::parse("td").unwrap();
-            let mut tds = tr.select(&td_selector);
-
-            let start_text = tds
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .text()
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-            let start = DateTime::parse_from_str(start_text, "%Y-%m-%d %H:%M:%S%z")
-                .map_err(|_| AtCoderClientError::HtmlParseError)?;
+        .enumerate()
+        .map(|(index, tr)| {
+            let row = columns.row(index, tr);
+
+            let start_text = columns.text(&row, START_TIME)?;
+            let start =
+                DateTime::parse_from_str(start_text, "%Y-%m-%d %H:%M:%S%z").map_err(|_| {
+                    row.error("%Y-%m-%d %H:%M:%S%z", start_text)
+                        .column(START_TIME)
+                })?;
             let start = start.timestamp() as u64;
 
-            let contest_td = tds.next().ok_or(AtCoderClientError::HtmlParseError)?;
-            let a_selector = Selector::parse("a").unwrap();
-            let contest_title = contest_td
-                .select(&a_selector)
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .text()
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-            let contest_link = contest_td
-                .select(&a_selector)
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .value()
-                .attr("href")
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-            let contest_id = contest_link
-                .rsplit('/')
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-
-            let duration_text = tds
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .text()
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-            let mut duration_split = duration_text.split(':');
-            let hours = duration_split
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .parse::<u64>()
-                .map_err(|_| AtCoderClientError::HtmlParseError)?;
-            let minutes = duration_split
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .parse::<u64>()
-                .map_err(|_| AtCoderClientError::HtmlParseError)?;
-            let duration = hours * 3600 + minutes * 60;
-
-            let rated_text = tds
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .text()
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?;
+            let contest_a = columns.link(&row, CONTEST_NAME)?;
+            let contest_title = contest_a.text().next().ok_or_else(|| {
+                row.error("contest title", &contest_a.html())
+                    .column(CONTEST_NAME)
+            })?;
+            let contest_id = columns.link_id(&row, CONTEST_NAME)?;
+
+            let duration_text = columns.text(&row, DURATION)?;
+            let duration = parse_duration(duration_text)
+                .ok_or_else(|| row.error("HH:MM", duration_text).column(DURATION))?;
+
+            let rated_text = columns.text(&row, RATED_RANGE)?;
 
             Ok(Contest {
-                id: contest_id.to_owned(),
+                id: contest_id,
                 start_epoch_second: start,
                 duration_second: duration,
                 title: contest_title.to_owned(),
@@ -88,9 +131,18 @@ pub fn scrape(html: &str) -> Result<Vec<Contest>, AtCoderClientError> {
         .collect::<Result<_, _>>()
 }
 
+/// Parses a duration of the form "HH:MM" into seconds.
+fn parse_duration(text: &str) -> Option<u64> {
+    let (hours, minutes) = text.split_once(':')?;
+    let hours = hours.trim().parse::<u64>().ok()?;
+    let minutes = minutes.trim().parse::<u64>().ok()?;
+    Some(hours * 3600 + minutes * 60)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use atcoder_ids::ContestId;
 
     #[test]
     fn scrape_contests_with_valid_html_returns_contests() {
@@ -100,7 +152,7 @@ mod tests {
         assert_eq!(contests.len(), 50);
 
         let expected = Contest {
-            id: "adt_all_20250522_3".to_string(),
+            id: ContestId::parse("adt_all_20250522_3").unwrap(),
             start_epoch_second: 1747913400,
             duration_second: 3600,
             title: "AtCoder Daily Training ALL 2025/05/22 20:30start".to_string(),
@@ -110,6 +162,51 @@ mod tests {
         assert_eq!(contests[0], expected);
     }
 
+    #[test]
+    fn scrape_schedule_returns_active_and_upcoming_contests() {
+        let page = include_str!("../../test_resources/contests_top_page.txt");
+        let schedule = scrape_schedule(page).expect("schedule scraping should succeed");
+
+        assert_eq!(
+            schedule
+                .active
+                .iter()
+                .map(|c| c.id.as_str())
+                .collect::<Vec<_>>(),
+            ["adt_all_20250529_3", "arc199"]
+        );
+        assert_eq!(schedule.upcoming.len(), 3);
+        assert_eq!(
+            schedule.upcoming[1],
+            Contest {
+                id: ContestId::parse("adt_easy_20250603_1").unwrap(),
+                start_epoch_second: 1748941200,
+                duration_second: 3600,
+                title: "AtCoder Daily Training EASY 2025/06/03 18:00start".to_string(),
+                rate_change: "-".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn scrape_schedule_without_sections_returns_empty_lists() {
+        let contents = "<html><body><p>No contests</p></body></html>";
+        let schedule = scrape_schedule(contents).expect("schedule scraping should succeed");
+
+        assert!(schedule.active.is_empty());
+        assert!(schedule.upcoming.is_empty());
+    }
+
+    #[test]
+    fn scrape_contest_page_returns_pagination() {
+        let contests_page_str = include_str!("../../test_resources/contests_page.txt");
+        let page = scrape_page(contests_page_str).expect("contest scraping should succeed");
+
+        assert_eq!(page.items.len(), 50);
+        assert_eq!(page.current_page, 1);
+        assert_eq!(page.total_pages, Some(58));
+    }
+
     #[test]
     fn scrape_contests_with_invalid_html_returns_empty_contents_error() {
         let contents =
@@ -118,4 +215,46 @@ mod tests {
 
         assert!(matches!(result, Err(AtCoderClientError::EmptyContents)));
     }
+
+    #[test]
+    fn scrape_contests_with_reordered_english_columns_returns_contests() {
+        let contents = r#"<table>
+            <thead><tr>
+                <th>Contest Name</th><th>Rated Range</th><th>Extra</th>
+                <th>Start Time<small>(local time)</small></th><th>Duration</th>
+            </tr></thead>
+            <tbody><tr>
+                <td><a href="/contests/adt_all_20250522_3">AtCoder Daily Training ALL 2025/05/22 20:30start</a></td>
+                <td>-</td><td>extra</td>
+                <td><time>2025-05-22 20:30:00+0900</time></td><td>01:00</td>
+            </tr></tbody>
+        </table>"#;
+        let contests = scrape(contents).expect("contest scraping should succeed");
+
+        let expected = Contest {
+            id: ContestId::parse("adt_all_20250522_3").unwrap(),
+            start_epoch_second: 1747913400,
+            duration_second: 3600,
+            title: "AtCoder Daily Training ALL 2025/05/22 20:30start".to_string(),
+            rate_change: "-".to_string(),
+        };
+        assert_eq!(contests, vec![expected]);
+    }
+
+    #[test]
+    fn scrape_contests_with_missing_column_returns_missing_column_error() {
+        let contents = r#"<table>
+            <thead><tr><th>開始時刻</th><th>コンテスト名</th><th>Rated対象</th></tr></thead>
+            <tbody><tr><td></td><td></td><td></td></tr></tbody>
+        </table>"#;
+        let result = scrape(contents);
+
+        assert!(matches!(
+            result,
+            Err(AtCoderClientError::MissingColumn {
+                column: DURATION,
+                ..
+            })
+        ));
+    }
 }
@@ -0,0 +1,100 @@
+use crate::error::AtCoderClientError;
+use scraper::{Html, Selector};
+
+/// Markers of an anti-bot challenge (e.g. Cloudflare's "Just a moment..." page) in the page's
+/// scripts. Only scripts are searched, since the rest of a page (e.g. submitted source code) may
+/// contain any text.
+const CHALLENGE_SCRIPT_MARKERS: &[&str] = &["_cf_chl_opt", "/cdn-cgi/challenge-platform/"];
+/// Selector of the element showing a challenge's progress.
+const CHALLENGE_ELEMENT: &str = "#challenge-running";
+/// Title prefixes of a challenge page.
+const CHALLENGE_TITLES: &[&str] = &["Just a moment", "Attention Required!"];
+/// Words in the title of AtCoder's maintenance notice.
+const MAINTENANCE_TITLES: &[&str] = &["メンテナンス", "Maintenance"];
+
+/// Checks whether the page is a maintenance notice or an anti-bot challenge served with HTTP 200
+/// instead of the requested page, and returns the corresponding error.
+pub fn detect(html: &str) -> Result<(), AtCoderClientError> {
+    let document = Html::parse_document(html);
+
+    let script_selector = Selector::parse("script").unwrap();
+    let challenge_script = document.select(&script_selector).any(|script| {
+        let text = script.text().collect::<String>();
+        let src = script.value().attr("src").unwrap_or_default();
+        CHALLENGE_SCRIPT_MARKERS
+            .iter()
+            .any(|marker| text.contains(marker) || src.contains(marker))
+    });
+    let challenge_element_selector = Selector::parse(CHALLENGE_ELEMENT).unwrap();
+    if challenge_script
+        || document
+            .select(&challenge_element_selector)
+            .next()
+            .is_some()
+    {
+        return Err(AtCoderClientError::Challenge);
+    }
+
+    let title_selector = Selector::parse("title").unwrap();
+    let title = document
+        .select(&title_selector)
+        .next()
+        .map(|t| t.text().collect::<String>())
+        .unwrap_or_default();
+    let title = title.trim();
+
+    if CHALLENGE_TITLES.iter().any(|t| title.starts_with(t)) {
+        return Err(AtCoderClientError::Challenge);
+    }
+    if MAINTENANCE_TITLES.iter().any(|t| title.contains(t)) {
+        return Err(AtCoderClientError::Maintenance);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scraper::scrape_submission_detail_page;
+    use atcoder_ids::{ContestId, SubmissionId};
+
+    #[test]
+    fn detect_maintenance_page_returns_maintenance_error() {
+        let page = include_str!("../../test_resources/maintenance_page.txt");
+        let error = detect(page).expect_err("maintenance page should be detected");
+
+        assert!(matches!(error, AtCoderClientError::Maintenance));
+        assert!(!error.is_empty_content());
+    }
+
+    #[test]
+    fn detect_challenge_page_returns_challenge_error() {
+        let page = include_str!("../../test_resources/challenge_page.txt");
+        let error = detect(page).expect_err("challenge page should be detected");
+
+        assert!(matches!(error, AtCoderClientError::Challenge));
+        assert!(!error.is_empty_content());
+    }
+
+    #[test]
+    fn detect_regular_pages_returns_ok() {
+        assert!(detect(include_str!("../../test_resources/contests_page.txt")).is_ok());
+        assert!(detect(include_str!("../../test_resources/submissions_page.txt")).is_ok());
+        assert!(detect(include_str!("../../test_resources/tasks_page.txt")).is_ok());
+    }
+
+    #[test]
+    fn detect_challenge_markers_in_source_code_returns_ok() {
+        let page = include_str!("../../test_resources/submission_detail_page.txt").replace(
+            "using namespace std;",
+            "// challenge-running _cf_chl_opt /cdn-cgi/challenge-platform/\nusing namespace std;",
+        );
+        assert!(detect(&page).is_ok());
+
+        let contest_id = ContestId::parse("adt_all_20250522_3").unwrap();
+        let submission_id = SubmissionId::new(66203973);
+        let detail = scrape_submission_detail_page(&page, &contest_id, submission_id).unwrap();
+        assert!(detail.source_code.contains("challenge-running"));
+    }
+}
@@ -0,0 +1,43 @@
+use crate::error::{AtCoderClientError, HtmlParseContext};
+use scraper::{Html, Selector};
+
+const PAGE: &str = "login";
+
+/// Parses the HTML of the AtCoder login page and returns the CSRF token of the login form.
+pub fn scrape(html: &str) -> Result<String, AtCoderClientError> {
+    let document = Html::parse_document(html);
+
+    let csrf_selector = Selector::parse(r#"input[name="csrf_token"]"#).unwrap();
+    let csrf_token = document
+        .select(&csrf_selector)
+        .next()
+        .and_then(|input| input.value().attr("value"))
+        .ok_or_else(|| HtmlParseContext::new(PAGE, "csrf_token input with a value"))?;
+
+    Ok(csrf_token.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrape_login_with_valid_html_returns_csrf_token() {
+        let contents = r#"<html><body><form action="" method="POST">
+            <input type="text" name="username" value="">
+            <input type="password" name="password">
+            <input type="hidden" name="csrf_token" value="abc+/=">
+            </form></body></html>"#;
+        let csrf_token = scrape(contents).expect("login scraping should succeed");
+
+        assert_eq!(csrf_token, "abc+/=");
+    }
+
+    #[test]
+    fn scrape_login_without_form_returns_html_parse_error() {
+        let contents = "<html><head><title>Login</title></head><body><p>Empty</p></body></html>";
+        let result = scrape(contents);
+
+        assert!(matches!(result, Err(AtCoderClientError::HtmlParseError(_))));
+    }
+}
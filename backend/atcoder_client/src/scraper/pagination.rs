@@ -0,0 +1,120 @@
+use regex::Regex;
+use scraper::{Html, Selector};
+use std::sync::LazyLock;
+
+static PAGE_PARAM_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[?&]page=(\d+)").unwrap());
+
+/// Position of a page within a paginated listing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pagination {
+    pub current_page: u32,
+    pub total_pages: Option<u32>,
+}
+
+/// Parses the pagination widget of a listing page.
+/// The numbered widget (`ul.pagination`) always links to the last page, so it gives the total.
+/// The Prev/Next pager (`ul.pager`) only tells the current page.
+/// A page without either widget is the only page of its listing.
+pub fn scrape(document: &Html) -> Pagination {
+    let pagination_selector = Selector::parse("ul.pagination").unwrap();
+    if let Some(ul) = document.select(&pagination_selector).next() {
+        let li_selector = Selector::parse("li").unwrap();
+        let pages = ul
+            .select(&li_selector)
+            .filter_map(|li| {
+                let page = li.text().collect::<String>().trim().parse::<u32>().ok()?;
+                let active = li.value().classes().any(|c| c == "active");
+                Some((page, active))
+            })
+            .collect::<Vec<_>>();
+
+        let current_page = pages
+            .iter()
+            .find(|(_, active)| *active)
+            .map(|(page, _)| *page)
+            .unwrap_or(1);
+        let total_pages = pages.iter().map(|(page, _)| *page).max().unwrap_or(1);
+        return Pagination {
+            current_page,
+            total_pages: Some(total_pages.max(current_page)),
+        };
+    }
+
+    let pager_selector = Selector::parse("ul.pager").unwrap();
+    if let Some(ul) = document.select(&pager_selector).next() {
+        let a_selector = Selector::parse("a").unwrap();
+        let current_page = ul
+            .select(&a_selector)
+            .find_map(|a| {
+                let href = a.value().attr("href")?;
+                let linked = PAGE_PARAM_RE.captures(href)?[1].parse::<u32>().ok()?;
+                if a.text().collect::<String>().contains("Next") {
+                    Some(linked.saturating_sub(1))
+                } else {
+                    Some(linked + 1)
+                }
+            })
+            .unwrap_or(1);
+        return Pagination {
+            current_page: current_page.max(1),
+            total_pages: None,
+        };
+    }
+
+    Pagination {
+        current_page: 1,
+        total_pages: Some(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrape_numbered_pagination_returns_current_and_total_pages() {
+        let document = Html::parse_document(include_str!("../../test_resources/contests_page.txt"));
+
+        assert_eq!(
+            scrape(&document),
+            Pagination {
+                current_page: 1,
+                total_pages: Some(58),
+            }
+        );
+    }
+
+    #[test]
+    fn scrape_pager_returns_current_page_only() {
+        let document =
+            Html::parse_document(include_str!("../../test_resources/submissions_page.txt"));
+        assert_eq!(
+            scrape(&document),
+            Pagination {
+                current_page: 1,
+                total_pages: None,
+            }
+        );
+
+        let document = Html::parse_document(
+            r#"<ul class="pager">
+                <li><a href="/contests/abc388/submissions?page=2">&lt; Prev</a></li>
+                <li><a href="/contests/abc388/submissions?page=4">Next &gt;</a></li>
+            </ul>"#,
+        );
+        assert_eq!(scrape(&document).current_page, 3);
+    }
+
+    #[test]
+    fn scrape_without_widget_returns_single_page() {
+        let document = Html::parse_document("<table><tbody></tbody></table>");
+
+        assert_eq!(
+            scrape(&document),
+            Pagination {
+                current_page: 1,
+                total_pages: Some(1),
+            }
+        );
+    }
+}
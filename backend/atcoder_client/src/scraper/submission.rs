@@ -1,148 +1,209 @@
-use crate::error::AtCoderClientError;
-use crate::models::Submission;
+use crate::error::{AtCoderClientError, HtmlParseContext};
+use crate::models::{JudgeStatus, ScrapedPage, Submission};
+use crate::scraper::columns::{Column, ColumnMap, Row, parent_table};
+use crate::scraper::pagination;
+use atcoder_ids::{ContestId, SubmissionId};
 use chrono::DateTime;
 use regex::Regex;
 use scraper::{Html, Selector};
+use std::sync::LazyLock;
+
+const PAGE: &str = "submission list";
+
+const SUBMISSION_TIME: &str = "submission_time";
+const TASK: &str = "task";
+const USER: &str = "user";
+const LANGUAGE: &str = "language";
+const SCORE: &str = "score";
+const CODE_SIZE: &str = "code_size";
+const STATUS: &str = "status";
+const EXEC_TIME: &str = "exec_time";
+const MEMORY: &str = "memory";
+
+static SUBMISSION_LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"submissions/\d+$").unwrap());
+
+const COLUMNS: &[Column] = &[
+    Column {
+        name: SUBMISSION_TIME,
+        labels: &["提出日時", "Submission Time"],
+        required: true,
+    },
+    Column {
+        name: TASK,
+        labels: &["問題", "Task"],
+        required: true,
+    },
+    Column {
+        name: USER,
+        labels: &["ユーザ", "User"],
+        required: true,
+    },
+    Column {
+        name: LANGUAGE,
+        labels: &["言語", "Language"],
+        required: true,
+    },
+    Column {
+        name: SCORE,
+        labels: &["得点", "Score"],
+        required: true,
+    },
+    Column {
+        name: CODE_SIZE,
+        labels: &["コード長", "Code Size"],
+        required: true,
+    },
+    Column {
+        name: STATUS,
+        labels: &["結果", "Status"],
+        required: true,
+    },
+    Column {
+        name: EXEC_TIME,
+        labels: &["実行時間", "Exec Time"],
+        required: false,
+    },
+    Column {
+        name: MEMORY,
+        labels: &["メモリ", "Memory"],
+        required: false,
+    },
+];
 
 /// Parses the HTML of the AtCoder submission list page and returns a list of submissions.
-pub fn scrape(html: &str, contest_id: &str) -> Result<Vec<Submission>, AtCoderClientError> {
+/// Cells are located by the `<thead>` labels, so reordered or extra columns are tolerated.
+pub fn scrape(html: &str, contest_id: &ContestId) -> Result<Vec<Submission>, AtCoderClientError> {
     let document = Html::parse_document(html);
+    scrape_rows(&document, contest_id)?
+        .into_iter()
+        .map(|row| row.map_err(AtCoderClientError::from))
+        .collect()
+}
 
+/// Parses the submission list page like [`scrape`], but skips malformed rows
+/// (e.g. judging rows such as "3/20 WJ") and reports them as rejected instead of failing the page.
+/// The position of the page is read from the pagination widget.
+pub fn scrape_lenient(
+    html: &str,
+    contest_id: &ContestId,
+) -> Result<ScrapedPage<Submission>, AtCoderClientError> {
+    let document = Html::parse_document(html);
+    let pagination = pagination::scrape(&document);
+
+    let mut page = ScrapedPage {
+        current_page: pagination.current_page,
+        total_pages: pagination.total_pages,
+        ..ScrapedPage::default()
+    };
+    for row in scrape_rows(&document, contest_id)? {
+        match row {
+            Ok(submission) => page.items.push(submission),
+            Err(reason) => page.rejected.push(reason),
+        }
+    }
+    Ok(page)
+}
+
+/// Locates the submission table and parses each of its rows.
+fn scrape_rows(
+    document: &Html,
+    contest_id: &ContestId,
+) -> Result<Vec<Result<Submission, HtmlParseContext>>, AtCoderClientError> {
     let tbody_selector = Selector::parse("tbody").unwrap();
     let tbody = document
         .select(&tbody_selector)
         .next()
         .ok_or(AtCoderClientError::EmptyContents)?;
 
+    let table = parent_table(tbody, PAGE)?;
+    let columns = ColumnMap::from_header(table, PAGE, COLUMNS)?;
+
     let tr_selector = Selector::parse("tr").unwrap();
-    tbody
+    Ok(tbody
         .select(&tr_selector)
-        .map(|tr| {
-            let td_selector = Selector::parse("td").unwrap();
-            let mut tds = tr.select(&td_selector);
-
-            let time_text = tds
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .text()
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-            let time = DateTime::parse_from_str(time_text, "%Y-%m-%d %H:%M:%S%z")
-                .map_err(|_| AtCoderClientError::HtmlParseError)?;
-            let epoch_second = time.timestamp() as u64;
-
-            let a_selector = Selector::parse("a").unwrap();
-
-            let problem_link = tds
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .select(&a_selector)
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .value()
-                .attr("href")
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-            let problem_id = problem_link
-                .rsplit('/')
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-
-            let user_link = tds
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .select(&a_selector)
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .value()
-                .attr("href")
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-            let user_id = user_link
-                .rsplit('/')
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-
-            let language = tds
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .text()
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-
-            let point = tds
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .text()
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .parse::<f64>()
-                .map_err(|_| AtCoderClientError::HtmlParseError)?;
-
-            let length = tds
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .text()
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .replace("Byte", "")
-                .trim()
-                .parse::<u64>()
-                .map_err(|_| AtCoderClientError::HtmlParseError)?;
-
-            let result = tds
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .text()
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-
-            let execution_time = tds
-                .next()
-                .and_then(|e| e.text().next())
-                .map(|s| s.replace("ms", ""))
-                .and_then(|s| s.trim().parse::<u64>().ok());
-
-            let re = Regex::new(r"submissions/\d+$").unwrap();
-            let submission_link = tr
-                .select(&a_selector)
-                .find(|e| match e.value().attr("href") {
-                    Some(href) => re.is_match(href),
-                    None => false,
-                })
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .value()
-                .attr("href")
-                .ok_or(AtCoderClientError::HtmlParseError)?;
-            let id = submission_link
-                .rsplit('/')
-                .next()
-                .ok_or(AtCoderClientError::HtmlParseError)?
-                .trim()
-                .parse::<u64>()
-                .map_err(|_| AtCoderClientError::HtmlParseError)?;
-
-            Ok(Submission {
-                id,
-                epoch_second,
-                problem_id: problem_id.to_owned(),
-                contest_id: contest_id.to_owned(),
-                user_id: user_id.to_owned(),
-                language: language.to_owned(),
-                point,
-                length,
-                result: result.to_owned(),
-                execution_time,
-            })
-        })
-        .collect()
+        .enumerate()
+        .map(|(index, tr)| parse_row(&columns, &columns.row(index, tr), contest_id))
+        .collect())
+}
+
+/// Parses a single row of the submission table.
+fn parse_row(
+    columns: &ColumnMap,
+    row: &Row,
+    contest_id: &ContestId,
+) -> Result<Submission, HtmlParseContext> {
+    let time_text = columns.text(row, SUBMISSION_TIME)?;
+    let time = DateTime::parse_from_str(time_text, "%Y-%m-%d %H:%M:%S%z").map_err(|_| {
+        row.error("%Y-%m-%d %H:%M:%S%z", time_text)
+            .column(SUBMISSION_TIME)
+    })?;
+    let epoch_second = time.timestamp() as u64;
+
+    let problem_id = columns.link_id(row, TASK)?;
+    let user_id = columns.link_id(row, USER)?;
+    let language = columns.text(row, LANGUAGE)?;
+
+    let score_text = columns.text(row, SCORE)?;
+    let point = score_text
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| row.error("numeric score", score_text).column(SCORE))?;
+
+    // e.g. "3531 Byte"
+    let code_size_text = columns.text(row, CODE_SIZE)?;
+    let length = code_size_text
+        .replace("Byte", "")
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| row.error("<n> Byte", code_size_text).column(CODE_SIZE))?;
+
+    let result = columns.text(row, STATUS)?;
+
+    // Absent when the status cell spans the remaining columns (e.g. CE)
+    let execution_time = columns
+        .optional_cell(row, EXEC_TIME)
+        .and_then(|e| e.text().next())
+        .map(|s| s.replace("ms", ""))
+        .and_then(|s| s.trim().parse::<u64>().ok());
+    let memory_kb = columns
+        .optional_cell(row, MEMORY)
+        .and_then(|e| e.text().next())
+        .map(|s| s.replace("KB", ""))
+        .and_then(|s| s.trim().parse::<u64>().ok());
+
+    let id = row
+        .links()
+        .into_iter()
+        .filter_map(|a| a.value().attr("href"))
+        .find(|href| SUBMISSION_LINK_RE.is_match(href))
+        .and_then(|href| href.rsplit('/').next())
+        .and_then(|id| id.trim().parse::<SubmissionId>().ok())
+        .ok_or_else(|| row.error("submission detail link", &row.text()))?;
+
+    Ok(Submission {
+        id,
+        epoch_second,
+        problem_id,
+        contest_id: contest_id.clone(),
+        user_id,
+        language: language.to_owned(),
+        point,
+        length,
+        result: JudgeStatus::parse(result),
+        execution_time,
+        memory_kb,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use atcoder_ids::{ProblemId, UserId};
 
     #[test]
     fn scrape_submissions_with_valid_html_returns_submissions() {
-        let contest_id = "adt_all_20250522_3";
+        let contest_id = &ContestId::parse("adt_all_20250522_3").unwrap();
         let submissions_page_str = include_str!("../../test_resources/submissions_page.txt");
         let submissions =
             scrape(submissions_page_str, contest_id).expect("submission scraping should succeed");
@@ -150,58 +211,62 @@ mod tests {
         assert_eq!(submissions.len(), 20);
 
         let expected_0 = Submission {
-            id: 66203973,
+            id: SubmissionId::new(66203973),
             epoch_second: 1748344794,
-            problem_id: "abc369_e".to_string(),
-            contest_id: contest_id.to_owned(),
-            user_id: "test1".to_owned(),
+            problem_id: ProblemId::parse("abc369_e").unwrap(),
+            contest_id: contest_id.clone(),
+            user_id: UserId::parse("test1").unwrap(),
             language: "C++ 20 (gcc 12.2)".to_owned(),
             point: 450.0,
             length: 3531,
-            result: "AC".to_owned(),
+            result: JudgeStatus::Accepted,
             execution_time: Some(224),
+            memory_kb: Some(7572),
         };
         assert_eq!(submissions[0], expected_0);
 
         let expected_4 = Submission {
-            id: 66194430,
+            id: SubmissionId::new(66194430),
             epoch_second: 1748314246,
-            problem_id: "abc281_a".to_string(),
-            contest_id: contest_id.to_owned(),
-            user_id: "test3".to_owned(),
+            problem_id: ProblemId::parse("abc281_a").unwrap(),
+            contest_id: contest_id.clone(),
+            user_id: UserId::parse("test3").unwrap(),
             language: "Python (PyPy 3.10-v7.3.12)".to_owned(),
             point: 0.0,
             length: 48,
-            result: "WA".to_owned(),
+            result: JudgeStatus::WrongAnswer,
             execution_time: Some(62),
+            memory_kb: Some(76624),
         };
         assert_eq!(submissions[4], expected_4);
 
         let expected_15 = Submission {
-            id: 66184195,
+            id: SubmissionId::new(66184195),
             epoch_second: 1748255371,
-            problem_id: "abc278_a".to_string(),
-            contest_id: contest_id.to_owned(),
-            user_id: "test1".to_owned(),
+            problem_id: ProblemId::parse("abc278_a").unwrap(),
+            contest_id: contest_id.clone(),
+            user_id: UserId::parse("test1").unwrap(),
             language: "C++ 20 (gcc 12.2)".to_owned(),
             point: 0.0,
             length: 2470,
-            result: "CE".to_owned(),
+            result: JudgeStatus::CompilationError,
             execution_time: None,
+            memory_kb: None,
         };
         assert_eq!(submissions[15], expected_15);
 
         let expected_19 = Submission {
-            id: 66178451,
+            id: SubmissionId::new(66178451),
             epoch_second: 1748238500,
-            problem_id: "abc344_c".to_string(),
-            contest_id: contest_id.to_owned(),
-            user_id: "test4".to_owned(),
+            problem_id: ProblemId::parse("abc344_c").unwrap(),
+            contest_id: contest_id.clone(),
+            user_id: UserId::parse("test4").unwrap(),
             language: "C (gcc 12.2.0)".to_owned(),
             point: 0.0,
             length: 1276,
-            result: "TLE".to_owned(),
+            result: JudgeStatus::TimeLimitExceeded,
             execution_time: Some(2207),
+            memory_kb: Some(1628),
         };
         assert_eq!(submissions[19], expected_19);
     }
@@ -210,8 +275,164 @@ mod tests {
     fn scrape_submissions_with_invalid_html_returns_empty_contents_error() {
         let contents =
             "<html><head><title>No contests</title></head><body><p>Empty</p></body></html>";
-        let result = scrape(contents, "test_contest");
+        let result = scrape(contents, &ContestId::parse("test_contest").unwrap());
 
         assert!(matches!(result, Err(AtCoderClientError::EmptyContents)));
     }
+
+    #[test]
+    fn scrape_submissions_with_reordered_english_columns_returns_submissions() {
+        let contents = r#"<table>
+            <thead><tr>
+                <th>User</th><th>Task</th><th>Status</th><th>Memory</th><th>Exec Time</th>
+                <th>Language</th><th>Score</th><th>Code Size</th><th>Submission Time</th><th></th>
+            </tr></thead>
+            <tbody><tr>
+                <td><a href="/users/test1">test1</a></td>
+                <td><a href="/contests/adt_all_20250522_3/tasks/abc369_e">H - Sightseeing Tour</a></td>
+                <td><span>AC</span></td><td>7572 KB</td><td>224 ms</td>
+                <td><a href="/contests/adt_all_20250522_3/submissions?f.Language=5001">C++ 20 (gcc 12.2)</a></td><td>450</td><td>3531 Byte</td>
+                <td><time>2025-05-27 20:19:54+0900</time></td>
+                <td><a href="/contests/adt_all_20250522_3/submissions/66203973">Detail</a></td>
+            </tr></tbody>
+        </table>"#;
+        let submissions = scrape(contents, &ContestId::parse("adt_all_20250522_3").unwrap())
+            .expect("submission scraping should succeed");
+
+        let expected = Submission {
+            id: SubmissionId::new(66203973),
+            epoch_second: 1748344794,
+            problem_id: ProblemId::parse("abc369_e").unwrap(),
+            contest_id: ContestId::parse("adt_all_20250522_3").unwrap(),
+            user_id: UserId::parse("test1").unwrap(),
+            language: "C++ 20 (gcc 12.2)".to_owned(),
+            point: 450.0,
+            length: 3531,
+            result: JudgeStatus::Accepted,
+            execution_time: Some(224),
+            memory_kb: Some(7572),
+        };
+        assert_eq!(submissions, vec![expected]);
+    }
+
+    #[test]
+    fn scrape_submissions_with_missing_column_returns_missing_column_error() {
+        let contents = r#"<table>
+            <thead><tr><th>提出日時</th><th>問題</th><th>ユーザ</th><th>言語</th></tr></thead>
+            <tbody><tr><td></td><td></td><td></td><td></td></tr></tbody>
+        </table>"#;
+        let result = scrape(contents, &ContestId::parse("test_contest").unwrap());
+
+        assert!(matches!(
+            result,
+            Err(AtCoderClientError::MissingColumn { column: SCORE, .. })
+        ));
+    }
+
+    #[test]
+    fn scrape_submissions_with_malformed_time_returns_html_parse_error_with_context() {
+        let contents = r#"<table>
+            <thead><tr>
+                <th>提出日時</th><th>問題</th><th>ユーザ</th><th>言語</th><th>得点</th>
+                <th>コード長</th><th>結果</th><th></th>
+            </tr></thead>
+            <tbody><tr>
+                <td><time>2025/05/27 20:19</time></td>
+                <td><a href="/contests/adt_all_20250522_3/tasks/abc369_e">H - Sightseeing Tour</a></td>
+                <td><a href="/users/test1">test1</a></td>
+                <td>C++ 20 (gcc 12.2)</td><td>450</td><td>3531 Byte</td><td>AC</td>
+                <td><a href="/contests/adt_all_20250522_3/submissions/66203973">詳細</a></td>
+            </tr></tbody>
+        </table>"#;
+        let result = scrape(contents, &ContestId::parse("adt_all_20250522_3").unwrap());
+
+        let Err(AtCoderClientError::HtmlParseError(context)) = result else {
+            panic!("expected HtmlParseError, got {result:?}");
+        };
+        assert_eq!(context.page, PAGE);
+        assert_eq!(context.row, Some(0));
+        assert_eq!(context.column, Some(SUBMISSION_TIME));
+        assert_eq!(context.snippet.as_deref(), Some("2025/05/27 20:19"));
+        assert_eq!(context.expected, "%Y-%m-%d %H:%M:%S%z");
+    }
+
+    #[test]
+    fn scrape_lenient_skips_malformed_rows_and_reports_them() {
+        let contents = r#"<table>
+            <thead><tr>
+                <th>提出日時</th><th>問題</th><th>ユーザ</th><th>言語</th><th>得点</th>
+                <th>コード長</th><th>結果</th><th>実行時間</th><th>メモリ</th><th></th>
+            </tr></thead>
+            <tbody>
+            <tr>
+                <td><time>2025-05-27 20:19:54+0900</time></td>
+                <td><a href="/contests/adt_all_20250522_3/tasks/abc369_e">H - Sightseeing Tour</a></td>
+                <td>(deleted)</td>
+                <td>C++ 20 (gcc 12.2)</td><td>450</td><td>3531 Byte</td><td>AC</td>
+                <td>224 ms</td><td>7572 KB</td>
+                <td><a href="/contests/adt_all_20250522_3/submissions/66203974">詳細</a></td>
+            </tr>
+            <tr>
+                <td><time>2025-05-27 20:19:54+0900</time></td>
+                <td><a href="/contests/adt_all_20250522_3/tasks/abc369_e">H - Sightseeing Tour</a></td>
+                <td><a href="/users/test1">test1</a></td>
+                <td>C++ 20 (gcc 12.2)</td><td>450</td><td>3531 Byte</td><td>AC</td>
+                <td>224 ms</td><td>7572 KB</td>
+                <td><a href="/contests/adt_all_20250522_3/submissions/66203973">詳細</a></td>
+            </tr>
+            </tbody>
+        </table>"#;
+
+        let page = scrape_lenient(contents, &ContestId::parse("adt_all_20250522_3").unwrap())
+            .expect("lenient submission scraping should succeed");
+
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.current_page, 1);
+        assert_eq!(page.total_pages, Some(1));
+        assert_eq!(page.items[0].id, SubmissionId::new(66203973));
+        assert_eq!(page.rejected.len(), 1);
+        assert_eq!(page.rejected[0].row, Some(0));
+        assert_eq!(page.rejected[0].column, Some(USER));
+
+        assert!(matches!(
+            scrape(contents, &ContestId::parse("adt_all_20250522_3").unwrap()),
+            Err(AtCoderClientError::HtmlParseError(_))
+        ));
+    }
+
+    #[test]
+    fn scrape_lenient_rejects_rows_with_malformed_ids() {
+        let contents = r#"<table>
+            <thead><tr>
+                <th>提出日時</th><th>問題</th><th>ユーザ</th><th>言語</th><th>得点</th>
+                <th>コード長</th><th>結果</th><th>実行時間</th><th>メモリ</th><th></th>
+            </tr></thead>
+            <tbody>
+            <tr>
+                <td><time>2025-05-27 20:19:54+0900</time></td>
+                <td><a href="/contests/adt_all_20250522_3/tasks/abc369">H - Sightseeing Tour</a></td>
+                <td><a href="/users/test1">test1</a></td>
+                <td>C++ 20 (gcc 12.2)</td><td>450</td><td>3531 Byte</td><td>AC</td>
+                <td>224 ms</td><td>7572 KB</td>
+                <td><a href="/contests/adt_all_20250522_3/submissions/66203974">詳細</a></td>
+            </tr>
+            <tr>
+                <td><time>2025-05-27 20:19:54+0900</time></td>
+                <td><a href="/contests/adt_all_20250522_3/tasks/abc369_e">H - Sightseeing Tour</a></td>
+                <td><a href="/users/test%201">test 1</a></td>
+                <td>C++ 20 (gcc 12.2)</td><td>450</td><td>3531 Byte</td><td>AC</td>
+                <td>224 ms</td><td>7572 KB</td>
+                <td><a href="/contests/adt_all_20250522_3/submissions/66203973">詳細</a></td>
+            </tr>
+            </tbody>
+        </table>"#;
+
+        let page = scrape_lenient(contents, &ContestId::parse("adt_all_20250522_3").unwrap())
+            .expect("lenient submission scraping should succeed");
+
+        assert!(page.items.is_empty());
+        let columns = page.rejected.iter().map(|c| c.column).collect::<Vec<_>>();
+        assert_eq!(columns, vec![Some(TASK), Some(USER)]);
+        assert_eq!(page.rejected[0].expected, "href ending with a valid ID");
+    }
 }
@@ -0,0 +1,281 @@
+use crate::error::{AtCoderClientError, HtmlParseContext};
+use crate::models::{JudgeStatus, SubmissionDetail, TestCaseResult};
+use crate::scraper::columns::{Column, ColumnMap, normalize};
+use atcoder_ids::{ContestId, SubmissionId};
+use chrono::DateTime;
+use scraper::{ElementRef, Html, Selector};
+use std::str::FromStr;
+
+const PAGE: &str = "submission detail";
+
+const SUBMISSION_TIME: &[&str] = &["提出日時", "Submission Time"];
+const TASK: &[&str] = &["問題", "Task"];
+const USER: &[&str] = &["ユーザ", "User"];
+const LANGUAGE: &[&str] = &["言語", "Language"];
+const EXEC_TIME: &[&str] = &["実行時間", "Exec Time"];
+const MEMORY: &[&str] = &["メモリ", "Memory"];
+const JUDGE_TIME: &[&str] = &["ジャッジ日時", "Judge Time"];
+
+const CASE_NAME: &str = "case_name";
+const CASE_STATUS: &str = "case_status";
+const CASE_EXEC_TIME: &str = "case_exec_time";
+const CASE_MEMORY: &str = "case_memory";
+
+const CASE_COLUMNS: &[Column] = &[
+    Column {
+        name: CASE_NAME,
+        labels: &["ケース名", "Case Name"],
+        required: true,
+    },
+    Column {
+        name: CASE_STATUS,
+        labels: &["結果", "Status"],
+        required: true,
+    },
+    Column {
+        name: CASE_EXEC_TIME,
+        labels: &["実行時間", "Exec Time"],
+        required: false,
+    },
+    Column {
+        name: CASE_MEMORY,
+        labels: &["メモリ", "Memory"],
+        required: false,
+    },
+];
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%z";
+
+/// Parses the HTML of an AtCoder submission detail page.
+/// The summary is read from the table containing `#judge-status`, and the test case verdicts
+/// from the table headed by the case name column (absent for CE or WJ submissions).
+pub fn scrape(
+    html: &str,
+    contest_id: &ContestId,
+    submission_id: SubmissionId,
+) -> Result<SubmissionDetail, AtCoderClientError> {
+    let document = Html::parse_document(html);
+
+    let status_selector = Selector::parse("#judge-status").unwrap();
+    let status_td = document
+        .select(&status_selector)
+        .next()
+        .ok_or(AtCoderClientError::EmptyContents)?;
+    let result = JudgeStatus::parse(&status_td.text().collect::<String>());
+
+    let info_table = status_td
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|e| e.value().name() == "table")
+        .ok_or_else(|| HtmlParseContext::new(PAGE, "<table> around #judge-status"))?;
+    let info = InfoTable(info_table);
+
+    let code_selector = Selector::parse("#submission-code").unwrap();
+    let source_code = document
+        .select(&code_selector)
+        .next()
+        .ok_or_else(|| HtmlParseContext::new(PAGE, "#submission-code"))?
+        .text()
+        .collect::<String>();
+
+    let submitted_epoch_second = parse_time(info.required_text(SUBMISSION_TIME)?)?;
+    let judged_epoch_second = info.text(JUDGE_TIME).map(parse_time).transpose()?;
+
+    let problem_id = info.link_id(TASK)?;
+    let user_id = info.link_id(USER)?;
+    let language = info.required_text(LANGUAGE)?;
+
+    let execution_time = info.text(EXEC_TIME).and_then(|s| parse_unit(s, "ms"));
+    let memory_kb = info.text(MEMORY).and_then(|s| parse_unit(s, "KB"));
+
+    Ok(SubmissionDetail {
+        id: submission_id,
+        contest_id: contest_id.clone(),
+        problem_id,
+        user_id,
+        language: language.trim().to_owned(),
+        result,
+        execution_time,
+        memory_kb,
+        source_code,
+        test_cases: scrape_test_cases(&document)?,
+        submitted_epoch_second,
+        judged_epoch_second,
+    })
+}
+
+/// Parses the per-test-case table, returning an empty list if the page has none.
+fn scrape_test_cases(document: &Html) -> Result<Vec<TestCaseResult>, HtmlParseContext> {
+    let table_selector = Selector::parse("table").unwrap();
+    let Some((table, columns)) = document.select(&table_selector).find_map(|table| {
+        ColumnMap::from_header(table, PAGE, CASE_COLUMNS)
+            .ok()
+            .map(|columns| (table, columns))
+    }) else {
+        return Ok(Vec::new());
+    };
+
+    let tr_selector = Selector::parse("tbody tr").unwrap();
+    table
+        .select(&tr_selector)
+        .enumerate()
+        .map(|(index, tr)| {
+            let row = columns.row(index, tr);
+
+            let name = columns.text(&row, CASE_NAME)?;
+            let status = columns.cell(&row, CASE_STATUS)?;
+            let execution_time = columns
+                .optional_cell(&row, CASE_EXEC_TIME)
+                .and_then(|e| e.text().next())
+                .and_then(|s| parse_unit(s, "ms"));
+            let memory_kb = columns
+                .optional_cell(&row, CASE_MEMORY)
+                .and_then(|e| e.text().next())
+                .and_then(|s| parse_unit(s, "KB"));
+
+            Ok(TestCaseResult {
+                name: name.trim().to_owned(),
+                result: JudgeStatus::parse(&status.text().collect::<String>()),
+                execution_time,
+                memory_kb,
+            })
+        })
+        .collect()
+}
+
+/// The key-value table of submission info, with labels in `<th>` and values in `<td>`.
+struct InfoTable<'a>(ElementRef<'a>);
+
+impl<'a> InfoTable<'a> {
+    /// Returns the value cell of the row labelled with one of `labels`.
+    fn cell(&self, labels: &[&str]) -> Option<ElementRef<'a>> {
+        let tr_selector = Selector::parse("tr").unwrap();
+        let th_selector = Selector::parse("th").unwrap();
+        let td_selector = Selector::parse("td").unwrap();
+
+        self.0.select(&tr_selector).find_map(|tr| {
+            let th = tr.select(&th_selector).next()?;
+            let label = normalize(&th.text().collect::<String>());
+            if labels.contains(&label.as_str()) {
+                tr.select(&td_selector).next()
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the first non-blank text node of the row labelled with one of `labels`.
+    fn text(&self, labels: &[&str]) -> Option<&'a str> {
+        self.cell(labels)?.text().find(|s| !s.trim().is_empty())
+    }
+
+    fn required_text(&self, labels: &[&'static str]) -> Result<&'a str, HtmlParseContext> {
+        self.text(labels)
+            .ok_or_else(|| HtmlParseContext::new(PAGE, "non-empty value").column(labels[0]))
+    }
+
+    /// Parses the last path segment of the first link in the row labelled with one of `labels` as an ID.
+    fn link_id<T: FromStr>(&self, labels: &[&'static str]) -> Result<T, HtmlParseContext> {
+        let a_selector = Selector::parse("a").unwrap();
+        let cell = self.cell(labels);
+        cell.and_then(|td| td.select(&a_selector).next())
+            .and_then(|a| a.value().attr("href"))
+            .and_then(|href| href.rsplit('/').next())
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(|| {
+                let context =
+                    HtmlParseContext::new(PAGE, "href ending with a valid ID").column(labels[0]);
+                match cell {
+                    Some(td) => context.snippet(&td.html()),
+                    None => context,
+                }
+            })
+    }
+}
+
+fn parse_time(text: &str) -> Result<u64, HtmlParseContext> {
+    DateTime::parse_from_str(text.trim(), TIME_FORMAT)
+        .map(|time| time.timestamp() as u64)
+        .map_err(|_| HtmlParseContext::new(PAGE, TIME_FORMAT).snippet(text))
+}
+
+/// Parses a value such as "224 ms" or "7572 KB".
+fn parse_unit(text: &str, unit: &str) -> Option<u64> {
+    text.replace(unit, "").trim().parse::<u64>().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrape_submission_detail_with_valid_html_returns_detail() {
+        let page = include_str!("../../test_resources/submission_detail_page.txt");
+        let detail = scrape(
+            page,
+            &ContestId::parse("adt_all_20250522_3").unwrap(),
+            SubmissionId::new(66203973),
+        )
+        .expect("submission detail scraping should succeed");
+
+        assert_eq!(detail.id, SubmissionId::new(66203973));
+        assert_eq!(detail.problem_id, "abc369_e");
+        assert_eq!(detail.user_id, "test1");
+        assert_eq!(detail.language, "C++ 20 (gcc 12.2)");
+        assert_eq!(detail.result, JudgeStatus::Accepted);
+        assert_eq!(detail.execution_time, Some(224));
+        assert_eq!(detail.memory_kb, Some(7572));
+        assert_eq!(detail.submitted_epoch_second, 1748344794);
+        assert_eq!(detail.judged_epoch_second, Some(1748344803));
+        assert!(detail.source_code.starts_with("#include <bits/stdc++.h>\n"));
+        assert!(detail.source_code.contains("cin >> n;"));
+
+        assert_eq!(detail.test_cases.len(), 4);
+        assert_eq!(
+            detail.test_cases[3],
+            TestCaseResult {
+                name: "random_00.txt".to_string(),
+                result: JudgeStatus::Accepted,
+                execution_time: Some(224),
+                memory_kb: Some(7572),
+            }
+        );
+    }
+
+    #[test]
+    fn scrape_submission_detail_without_test_cases_returns_empty_list() {
+        let contents = r#"<html><body>
+            <pre id="submission-code">int main() {}</pre>
+            <table>
+                <tr><th>提出日時</th><td><time>2025-05-27 20:19:54+0900</time></td></tr>
+                <tr><th>問題</th><td><a href="/contests/adt_all_20250522_3/tasks/abc278_a">A - Shift</a></td></tr>
+                <tr><th>ユーザ</th><td><a href="/users/test1">test1</a></td></tr>
+                <tr><th>言語</th><td>C++ 20 (gcc 12.2)</td></tr>
+                <tr><th>結果</th><td id="judge-status"><span>CE</span></td></tr>
+            </table>
+        </body></html>"#;
+        let detail = scrape(
+            contents,
+            &ContestId::parse("adt_all_20250522_3").unwrap(),
+            SubmissionId::new(66184195),
+        )
+        .expect("submission detail scraping should succeed");
+
+        assert_eq!(detail.result, JudgeStatus::CompilationError);
+        assert_eq!(detail.execution_time, None);
+        assert_eq!(detail.judged_epoch_second, None);
+        assert!(detail.test_cases.is_empty());
+    }
+
+    #[test]
+    fn scrape_submission_detail_with_invalid_html_returns_empty_contents_error() {
+        let contents = "<html><head><title>Not found</title></head><body></body></html>";
+        let result = scrape(
+            contents,
+            &ContestId::parse("adt_all_20250522_3").unwrap(),
+            SubmissionId::new(1),
+        );
+
+        assert!(matches!(result, Err(AtCoderClientError::EmptyContents)));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::error::AtCoderClientError;
+use crate::models::ContestTask;
+use crate::scraper::columns::{Column, ColumnMap, parent_table};
+use scraper::{Html, Selector};
+
+const PAGE: &str = "task list";
+
+const LABEL: &str = "label";
+const TASK_NAME: &str = "task_name";
+const TIME_LIMIT: &str = "time_limit";
+const MEMORY_LIMIT: &str = "memory_limit";
+
+const COLUMNS: &[Column] = &[
+    // The label column has an empty header in both languages
+    Column {
+        name: LABEL,
+        labels: &[""],
+        required: true,
+    },
+    Column {
+        name: TASK_NAME,
+        labels: &["問題名", "Task Name"],
+        required: true,
+    },
+    Column {
+        name: TIME_LIMIT,
+        labels: &["実行時間制限", "Time Limit"],
+        required: true,
+    },
+    Column {
+        name: MEMORY_LIMIT,
+        labels: &["メモリ制限", "Memory Limit"],
+        required: true,
+    },
+];
+
+/// Parses the HTML of the AtCoder contest task list page and returns a list of tasks.
+/// Cells are located by the `<thead>` labels, so reordered or extra columns are tolerated.
+pub fn scrape(html: &str) -> Result<Vec<ContestTask>, AtCoderClientError> {
+    let document = Html::parse_document(html);
+
+    let tbody_selector = Selector::parse("tbody").unwrap();
+    let tbody = document
+        .select(&tbody_selector)
+        .next()
+        .ok_or(AtCoderClientError::EmptyContents)?;
+
+    let table = parent_table(tbody, PAGE)?;
+    let columns = ColumnMap::from_header(table, PAGE, COLUMNS)?;
+
+    let tr_selector = Selector::parse("tr").unwrap();
+    tbody
+        .select(&tr_selector)
+        .enumerate()
+        .map(|(index, tr)| {
+            let row = columns.row(index, tr);
+
+            let label_a = columns.link(&row, LABEL)?;
+            let label = label_a
+                .text()
+                .next()
+                .ok_or_else(|| row.error("task label", &label_a.html()).column(LABEL))?
+                .trim();
+
+            let task_a = columns.link(&row, TASK_NAME)?;
+            let title = task_a
+                .text()
+                .next()
+                .ok_or_else(|| row.error("task title", &task_a.html()).column(TASK_NAME))?
+                .trim();
+            let problem_id = columns.link_id(&row, TASK_NAME)?;
+
+            // e.g. "2 sec", "3.5 sec"
+            let time_limit_text = columns.text(&row, TIME_LIMIT)?;
+            let time_limit_secs = time_limit_text
+                .replace("sec", "")
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| row.error("<n> sec", time_limit_text).column(TIME_LIMIT))?;
+            let time_limit_millis = (time_limit_secs * 1000.0).round() as u64;
+
+            // e.g. "1024 MB"
+            let memory_limit_text = columns.text(&row, MEMORY_LIMIT)?;
+            let memory_limit_mb = memory_limit_text
+                .replace("MB", "")
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| row.error("<n> MB", memory_limit_text).column(MEMORY_LIMIT))?;
+
+            Ok(ContestTask {
+                label: label.to_owned(),
+                problem_id,
+                title: title.to_owned(),
+                time_limit_millis,
+                memory_limit_mb,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atcoder_ids::ProblemId;
+
+    #[test]
+    fn scrape_tasks_with_valid_html_returns_tasks() {
+        let tasks_page_str = include_str!("../../test_resources/tasks_page.txt");
+        let tasks = scrape(tasks_page_str).expect("task scraping should succeed");
+
+        assert_eq!(tasks.len(), 9);
+
+        let expected_0 = ContestTask {
+            label: "A".to_string(),
+            problem_id: ProblemId::parse("abc281_a").unwrap(),
+            title: "Count Down".to_string(),
+            time_limit_millis: 2000,
+            memory_limit_mb: 1024,
+        };
+        assert_eq!(tasks[0], expected_0);
+
+        let expected_8 = ContestTask {
+            label: "I".to_string(),
+            problem_id: ProblemId::parse("abc254_f").unwrap(),
+            title: "Rectangle GCD".to_string(),
+            time_limit_millis: 3500,
+            memory_limit_mb: 1024,
+        };
+        assert_eq!(tasks[8], expected_8);
+    }
+
+    #[test]
+    fn scrape_tasks_with_invalid_html_returns_empty_contents_error() {
+        let contents = "<html><head><title>No tasks</title></head><body><p>Empty</p></body></html>";
+        let result = scrape(contents);
+
+        assert!(matches!(result, Err(AtCoderClientError::EmptyContents)));
+    }
+
+    #[test]
+    fn scrape_tasks_with_english_columns_returns_tasks() {
+        let contents = r#"<table>
+            <thead><tr>
+                <th></th><th>Task Name</th><th>Time Limit</th><th>Memory Limit</th><th></th>
+            </tr></thead>
+            <tbody><tr>
+                <td><a href="/contests/adt_all_20250522_3/tasks/abc369_e">H</a></td>
+                <td><a href="/contests/adt_all_20250522_3/tasks/abc369_e">Sightseeing Tour</a></td>
+                <td>3 sec</td><td>1024 MB</td>
+                <td><a href="/contests/adt_all_20250522_3/submit?taskScreenName=abc369_e">Submit</a></td>
+            </tr></tbody>
+        </table>"#;
+        let tasks = scrape(contents).expect("task scraping should succeed");
+
+        let expected = ContestTask {
+            label: "H".to_string(),
+            problem_id: ProblemId::parse("abc369_e").unwrap(),
+            title: "Sightseeing Tour".to_string(),
+            time_limit_millis: 3000,
+            memory_limit_mb: 1024,
+        };
+        assert_eq!(tasks, vec![expected]);
+    }
+}
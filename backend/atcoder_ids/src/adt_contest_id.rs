@@ -0,0 +1,273 @@
+use crate::{ContestId, IdParseError, contest_id::ADT_PREFIX};
+use chrono::{NaiveDate, NaiveTime};
+use std::{fmt, str::FromStr};
+
+/// Date format in ADT contest IDs (e.g. "20250522").
+const ID_DATE_FORMAT: &str = "%Y%m%d";
+/// Date format in ADT contest titles (e.g. "2025/05/22").
+const TITLE_DATE_FORMAT: &str = "%Y/%m/%d";
+/// Start time format in ADT contest titles (e.g. "20:30").
+const TITLE_TIME_FORMAT: &str = "%H:%M";
+/// Common prefix of ADT contest titles.
+const TITLE_PREFIX: &str = "AtCoder Daily Training ";
+/// Suffix after the start time in ADT contest titles.
+const TITLE_SUFFIX: &str = "start";
+
+/// Difficulty tier of an AtCoder Daily Training contest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AdtTier {
+    Easy,
+    Medium,
+    Hard,
+    /// All problems of the day's Easy, Medium and Hard contests.
+    All,
+}
+
+impl AdtTier {
+    /// All tiers, from easiest to the combined one.
+    pub const TIERS: [AdtTier; 4] = [AdtTier::Easy, AdtTier::Medium, AdtTier::Hard, AdtTier::All];
+
+    /// Name used in contest IDs (e.g. "easy").
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AdtTier::Easy => "easy",
+            AdtTier::Medium => "medium",
+            AdtTier::Hard => "hard",
+            AdtTier::All => "all",
+        }
+    }
+
+    /// Name used in contest titles (e.g. "EASY").
+    pub fn title_label(self) -> &'static str {
+        match self {
+            AdtTier::Easy => "EASY",
+            AdtTier::Medium => "MEDIUM",
+            AdtTier::Hard => "HARD",
+            AdtTier::All => "ALL",
+        }
+    }
+
+    fn from_id_part(part: &str) -> Option<Self> {
+        Self::TIERS.into_iter().find(|tier| tier.as_str() == part)
+    }
+
+    fn from_title_label(label: &str) -> Option<Self> {
+        Self::TIERS
+            .into_iter()
+            .find(|tier| tier.title_label() == label)
+    }
+}
+
+impl fmt::Display for AdtTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A parsed AtCoder Daily Training contest ID of the form "adt_{tier}_{YYYYMMDD}_{slot}",
+/// such as "adt_all_20250522_3".
+/// Ordered by date, then slot, then tier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AdtContestId {
+    pub date: NaiveDate,
+    /// 1-based time slot within the day.
+    pub slot: u8,
+    pub tier: AdtTier,
+}
+
+impl AdtContestId {
+    const KIND: &'static str = "ADT contest ID";
+
+    /// Parses an ADT contest ID.
+    pub fn parse(value: &str) -> Result<Self, IdParseError> {
+        Self::parse_parts(value).ok_or_else(|| IdParseError::new(Self::KIND, value))
+    }
+
+    fn parse_parts(value: &str) -> Option<Self> {
+        let mut parts = value.strip_prefix(ADT_PREFIX)?.split('_');
+        let tier = AdtTier::from_id_part(parts.next()?)?;
+        let date = parts.next()?;
+        let slot = parts.next()?;
+        if parts.next().is_some() || date.len() != 8 || !slot.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let date = NaiveDate::parse_from_str(date, ID_DATE_FORMAT).ok()?;
+        let slot = slot.parse::<u8>().ok().filter(|slot| *slot > 0)?;
+        Some(Self { date, slot, tier })
+    }
+
+    /// Returns the generic contest ID.
+    pub fn contest_id(&self) -> ContestId {
+        ContestId::parse(&self.to_string()).expect("formatted ADT contest ID should be valid")
+    }
+
+    /// Whether `title` is the title of this contest (same tier and date).
+    /// Titles only carry the start time, so the slot is not compared.
+    pub fn matches_title(&self, title: &AdtContestTitle) -> bool {
+        self.tier == title.tier && self.date == title.date
+    }
+}
+
+impl FromStr for AdtContestId {
+    type Err = IdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&ContestId> for AdtContestId {
+    type Error = IdParseError;
+
+    fn try_from(contest_id: &ContestId) -> Result<Self, Self::Error> {
+        Self::parse(contest_id.as_str())
+    }
+}
+
+impl fmt::Display for AdtContestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "adt_{}_{}_{}",
+            self.tier,
+            self.date.format(ID_DATE_FORMAT),
+            self.slot
+        )
+    }
+}
+
+/// A parsed ADT contest title such as "AtCoder Daily Training ALL 2025/05/22 20:30start".
+/// The title is the same on the Japanese and English pages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AdtContestTitle {
+    pub tier: AdtTier,
+    pub date: NaiveDate,
+    /// Start time in JST.
+    pub start_time: NaiveTime,
+}
+
+impl AdtContestTitle {
+    const KIND: &'static str = "ADT contest title";
+
+    /// Parses an ADT contest title.
+    pub fn parse(title: &str) -> Result<Self, IdParseError> {
+        Self::parse_parts(title.trim()).ok_or_else(|| IdParseError::new(Self::KIND, title))
+    }
+
+    fn parse_parts(title: &str) -> Option<Self> {
+        let rest = title
+            .strip_prefix(TITLE_PREFIX)?
+            .strip_suffix(TITLE_SUFFIX)?;
+        let mut parts = rest.split(' ');
+        let tier = AdtTier::from_title_label(parts.next()?)?;
+        let date = NaiveDate::parse_from_str(parts.next()?, TITLE_DATE_FORMAT).ok()?;
+        let start_time = NaiveTime::parse_from_str(parts.next()?, TITLE_TIME_FORMAT).ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            tier,
+            date,
+            start_time,
+        })
+    }
+}
+
+impl FromStr for AdtContestTitle {
+    type Err = IdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for AdtContestTitle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{} {} {}{}",
+            TITLE_PREFIX,
+            self.tier.title_label(),
+            self.date.format(TITLE_DATE_FORMAT),
+            self.start_time.format(TITLE_TIME_FORMAT),
+            TITLE_SUFFIX
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_format_adt_contest_id() {
+        let id = AdtContestId::parse("adt_medium_20250603_2").unwrap();
+        assert_eq!(id.tier, AdtTier::Medium);
+        assert_eq!(id.date, NaiveDate::from_ymd_opt(2025, 6, 3).unwrap());
+        assert_eq!(id.slot, 2);
+        assert_eq!(id.to_string(), "adt_medium_20250603_2");
+        assert_eq!(id.contest_id(), "adt_medium_20250603_2");
+    }
+
+    #[test]
+    fn parse_rejects_non_adt_or_malformed_ids() {
+        for id in [
+            "abc407",
+            "adt_expert_20250603_1",
+            "adt_all_2025063_1",
+            "adt_all_20250631_1",
+            "adt_all_20250603_0",
+            "adt_all_20250603",
+            "adt_all_20250603_1_2",
+        ] {
+            assert!(
+                AdtContestId::parse(id).is_err(),
+                "{id:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn adt_contest_ids_order_by_date_then_slot_then_tier() {
+        let mut ids = [
+            "adt_all_20250522_1",
+            "adt_easy_20250522_2",
+            "adt_hard_20250521_3",
+            "adt_easy_20250522_1",
+        ]
+        .map(|id| AdtContestId::parse(id).unwrap());
+        ids.sort();
+
+        let ids = ids.map(|id| id.to_string());
+        assert_eq!(
+            ids,
+            [
+                "adt_hard_20250521_3",
+                "adt_easy_20250522_1",
+                "adt_all_20250522_1",
+                "adt_easy_20250522_2",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_and_format_title() {
+        let title = "AtCoder Daily Training ALL 2025/05/22 20:30start";
+        let parsed = AdtContestTitle::parse(title).unwrap();
+        assert_eq!(parsed.tier, AdtTier::All);
+        assert_eq!(
+            parsed.start_time,
+            NaiveTime::from_hms_opt(20, 30, 0).unwrap()
+        );
+        assert_eq!(parsed.to_string(), title);
+
+        let id = AdtContestId::parse("adt_all_20250522_3").unwrap();
+        assert!(id.matches_title(&parsed));
+        let other = AdtContestId::parse("adt_easy_20250522_3").unwrap();
+        assert!(!other.matches_title(&parsed));
+
+        assert!(AdtContestTitle::parse("AtCoder Beginner Contest 407").is_err());
+    }
+}
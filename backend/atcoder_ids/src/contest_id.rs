@@ -0,0 +1,63 @@
+use crate::{AdtContestId, impl_string_id, is_slug_char};
+use serde::{Deserialize, Serialize};
+
+/// Prefix of AtCoder Daily Training contest IDs.
+pub(crate) const ADT_PREFIX: &str = "adt_";
+
+/// An AtCoder contest ID such as "abc407", "jsc2019-qual" or "adt_all_20250529_3".
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ContestId(String);
+
+impl ContestId {
+    const KIND: &'static str = "contest ID";
+
+    fn validate(value: &str) -> bool {
+        !value.is_empty() && value.chars().all(is_slug_char)
+    }
+
+    /// Whether this is an AtCoder Daily Training contest.
+    pub fn is_adt(&self) -> bool {
+        self.0.starts_with(ADT_PREFIX)
+    }
+
+    /// Parses the tier, date and slot if this is a well-formed ADT contest ID.
+    pub fn adt(&self) -> Option<AdtContestId> {
+        AdtContestId::try_from(self).ok()
+    }
+}
+
+impl_string_id!(ContestId);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_contest_slugs() {
+        for id in ["abc407", "jsc2019-qual", "adt_all_20250529_3"] {
+            assert_eq!(ContestId::parse(id).unwrap().as_str(), id);
+        }
+        assert!(ContestId::parse("adt_easy_20250603_1").unwrap().is_adt());
+        assert!(!ContestId::parse("abc407").unwrap().is_adt());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_ids() {
+        for id in ["", "abc407/submissions", "abc 407", "abc407?lang=ja"] {
+            let err = ContestId::parse(id).unwrap_err();
+            assert_eq!(err.kind, "contest ID");
+        }
+    }
+
+    #[test]
+    fn serde_uses_plain_string_and_validates() {
+        let id = ContestId::parse("abc407").unwrap();
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""abc407""#);
+        assert_eq!(
+            serde_json::from_str::<ContestId>(r#""abc407""#).unwrap(),
+            id
+        );
+        assert!(serde_json::from_str::<ContestId>(r#""../abc407""#).is_err());
+    }
+}
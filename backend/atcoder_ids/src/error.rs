@@ -0,0 +1,18 @@
+/// A value that is not a well-formed AtCoder ID of the expected kind.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid {kind}: {value:?}")]
+pub struct IdParseError {
+    /// Kind of ID being parsed (e.g. "contest ID").
+    pub kind: &'static str,
+    /// The rejected value.
+    pub value: String,
+}
+
+impl IdParseError {
+    pub(crate) fn new(kind: &'static str, value: &str) -> Self {
+        Self {
+            kind,
+            value: value.to_owned(),
+        }
+    }
+}
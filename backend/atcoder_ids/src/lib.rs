@@ -0,0 +1,96 @@
+mod adt_contest_id;
+mod contest_id;
+mod error;
+mod problem_id;
+mod submission_id;
+mod user_id;
+
+pub use adt_contest_id::{AdtContestId, AdtContestTitle, AdtTier};
+pub use contest_id::ContestId;
+pub use error::IdParseError;
+pub use problem_id::ProblemId;
+pub use submission_id::SubmissionId;
+pub use user_id::UserId;
+
+/// Implements the conversions shared by the string-backed IDs.
+/// The type must provide `fn validate(&str) -> bool` and a `KIND` name for errors.
+macro_rules! impl_string_id {
+    ($name:ident) => {
+        impl $name {
+            /// Parses and validates the ID.
+            pub fn parse(value: &str) -> Result<Self, $crate::IdParseError> {
+                if Self::validate(value) {
+                    Ok(Self(value.to_owned()))
+                } else {
+                    Err($crate::IdParseError::new(Self::KIND, value))
+                }
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = $crate::IdParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::parse(s)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = $crate::IdParseError;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                if Self::validate(&value) {
+                    Ok(Self(value))
+                } else {
+                    Err($crate::IdParseError::new(Self::KIND, &value))
+                }
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::borrow::Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+use impl_string_id;
+
+/// Characters allowed in contest and problem IDs.
+fn is_slug_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
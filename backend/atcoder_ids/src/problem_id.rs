@@ -0,0 +1,60 @@
+use crate::{impl_string_id, is_slug_char};
+use serde::{Deserialize, Serialize};
+
+/// An AtCoder problem ID of the form "{prefix}_{index}", such as "abc369_e" or "arc001_1".
+/// The prefix usually matches the contest ID, but not always (e.g. "jsc2019_qual_a").
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ProblemId(String);
+
+impl ProblemId {
+    const KIND: &'static str = "problem ID";
+
+    fn validate(value: &str) -> bool {
+        value.chars().all(is_slug_char)
+            && value
+                .rsplit_once('_')
+                .is_some_and(|(prefix, index)| !prefix.is_empty() && !index.is_empty())
+    }
+
+    /// The part before the last underscore (e.g. "abc369" for "abc369_e").
+    pub fn prefix(&self) -> &str {
+        self.split().0
+    }
+
+    /// The problem index within its contest (e.g. "e" for "abc369_e").
+    pub fn index(&self) -> &str {
+        self.split().1
+    }
+
+    fn split(&self) -> (&str, &str) {
+        self.0
+            .rsplit_once('_')
+            .expect("ProblemId is validated to contain an underscore")
+    }
+}
+
+impl_string_id!(ProblemId);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_splits_prefix_and_index() {
+        let id = ProblemId::parse("jsc2019_qual_a").unwrap();
+        assert_eq!(id.prefix(), "jsc2019_qual");
+        assert_eq!(id.index(), "a");
+
+        let id: ProblemId = "arc001_1".parse().unwrap();
+        assert_eq!((id.prefix(), id.index()), ("arc001", "1"));
+    }
+
+    #[test]
+    fn parse_rejects_malformed_ids() {
+        for id in ["", "abc369", "_e", "abc369_", "abc369_e/editorial"] {
+            let err = ProblemId::parse(id).unwrap_err();
+            assert_eq!(err.kind, "problem ID");
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::IdParseError;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// An AtCoder submission ID. IDs increase over time across all contests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SubmissionId(u64);
+
+impl SubmissionId {
+    pub const fn new(id: u64) -> Self {
+        Self(id)
+    }
+
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for SubmissionId {
+    fn from(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+impl FromStr for SubmissionId {
+    type Err = IdParseError;
+
+    /// Parses a decimal ID; signs and surrounding whitespace are rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(IdParseError::new("submission ID", s));
+        }
+        s.parse()
+            .map(Self)
+            .map_err(|_| IdParseError::new("submission ID", s))
+    }
+}
+
+impl fmt::Display for SubmissionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_only_decimal_digits() {
+        assert_eq!("65417085".parse(), Ok(SubmissionId::new(65417085)));
+        for id in ["", "+1", " 1", "1a", "99999999999999999999"] {
+            assert!(
+                id.parse::<SubmissionId>().is_err(),
+                "{id:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn serde_uses_plain_number() {
+        let id = SubmissionId::new(42);
+        assert_eq!(serde_json::to_string(&id).unwrap(), "42");
+        assert_eq!(serde_json::from_str::<SubmissionId>("42").unwrap(), id);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::impl_string_id;
+use serde::{Deserialize, Serialize};
+
+/// Maximum length of an AtCoder user name.
+const MAX_LEN: usize = 16;
+
+/// An AtCoder user name: up to 16 ASCII alphanumerics or underscores.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct UserId(String);
+
+impl UserId {
+    const KIND: &'static str = "user ID";
+
+    fn validate(value: &str) -> bool {
+        !value.is_empty()
+            && value.len() <= MAX_LEN
+            && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+}
+
+impl_string_id!(UserId);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_user_names() {
+        for id in ["tourist", "Test_User1", "a"] {
+            assert_eq!(UserId::parse(id).unwrap().to_string(), id);
+        }
+    }
+
+    #[test]
+    fn parse_rejects_malformed_ids() {
+        for id in ["", "user-name", "user/name", "a_very_long_user_name"] {
+            let err = UserId::parse(id).unwrap_err();
+            assert_eq!(err.kind, "user ID");
+        }
+    }
+}
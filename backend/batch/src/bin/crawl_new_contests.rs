@@ -1,11 +1,23 @@
 use atcoder_problems_adt_sync_batch::{
-    client::{init_atcoder_client, init_ddb_service},
+    client::{
+        init_atcoder_client, init_contest_categories, init_retry_policy, warn_if_session_expiring,
+    },
+    constants::MAX_TASK_BACKFILL_CONTESTS,
     crawler::ContestCrawler,
     dto::AdtContestDto,
+    report::TierCounts,
+};
+use chrono::Utc;
+use ddb_client::{ContestStore, StoreBackend};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
 };
 
-/// Main function to crawl AtCoder contests and write them to DynamoDB.
-/// Skips already stored contests using the latest contest ID.
+/// Main function to crawl AtCoder contests and write them to the store.
+/// Skips already stored contests, crawling each configured category until a known contest,
+/// fills in the problems of stored contests that have none yet (a limited number per run),
+/// and pre-registers ADT contests that have not ended yet.
 #[tokio::main]
 async fn main() {
     // Initialize logging
@@ -22,12 +34,16 @@ async fn main() {
             return;
         }
     };
+    if let Err(e) = warn_if_session_expiring(&atcoder_client) {
+        log::error!("{}", e);
+        return;
+    }
 
-    // Initialize DynamoDB service
-    let ddb_service = match init_ddb_service().await {
-        Ok(service) => {
-            log::info!("Successfully created DynamoDB service");
-            service
+    // Initialize the store (DynamoDB or SQLite)
+    let store = match StoreBackend::from_env().await {
+        Ok(store) => {
+            log::info!("Successfully opened {} store", store.name());
+            store
         }
         Err(e) => {
             log::error!("{}", e);
@@ -35,37 +51,128 @@ async fn main() {
         }
     };
 
-    // Fetch the latest contest ID from DynamoDB
-    let last_fetched_contest_id = match ddb_service.get_contests(Some(1)).await {
-        Ok(records) if !records.is_empty() => {
-            let latest = &records[0];
-            log::info!("Successfully fetched latest contest: {}", latest.contest_id);
-            Some(latest.contest_id.clone())
+    // Read the contest categories to crawl
+    let categories = match init_contest_categories() {
+        Ok(categories) => {
+            log::info!("Crawling contest categories: {:?}", categories);
+            categories
         }
-        Ok(_) => {
-            log::warn!("No contests found in DynamoDB, starting from scratch");
-            None
+        Err(e) => {
+            log::error!("{}", e);
+            return;
+        }
+    };
+
+    // Fetch the stored contests
+    let stored_contests = match store.get_contests(None).await {
+        Ok(records) => {
+            if records.is_empty() {
+                log::warn!("No contests found in the store, starting from scratch");
+            } else {
+                log::info!(
+                    "Successfully fetched stored contests: {} records",
+                    records.len()
+                );
+            }
+            records
+                .into_iter()
+                .map(|r| (r.contest_id.clone(), r))
+                .collect::<HashMap<_, _>>()
         }
         Err(err) => {
-            log::error!("Failed to fetch latest contest from DynamoDB: {}", err);
+            log::error!("Failed to fetch stored contests: {}", err);
             return;
         }
     };
 
+    // Every stored contest stops the crawl; those without problems are backfilled below
+    let known_contest_ids = stored_contests.keys().cloned().collect::<HashSet<_>>();
+
     // Initialize ContestCrawler with AtCoder client
-    let contest_crawler = ContestCrawler::new(atcoder_client);
+    let contest_crawler =
+        ContestCrawler::new(atcoder_client.clone(), init_retry_policy(), categories);
 
-    // Crawl contests until the last fetched contest ID
-    let mut contests = match contest_crawler
-        .crawl(last_fetched_contest_id.as_deref())
-        .await
-    {
+    // Crawl each category until a stored contest
+    let mut contests = match contest_crawler.crawl(&known_contest_ids).await {
         Ok(contests) => contests,
         Err(_) => return,
     };
     contests.sort_by_key(|c| c.start_epoch_second);
 
-    let contest_write_records = AdtContestDto::from_new_contests(contests)
+    // Stored contests that have started but have no problems yet: pre-registered ones, ones whose
+    // tasks failed to fetch, and ones stored before problems were recorded. The newest are
+    // backfilled first, a limited number per run, so the backfill resumes on the next run.
+    let now = u64::try_from(Utc::now().timestamp()).unwrap_or_default();
+    let mut backfill_contests = stored_contests
+        .values()
+        .filter(|r| r.problem_ids.is_empty() && r.start_epoch_second() <= now)
+        .collect::<Vec<_>>();
+    backfill_contests.sort_by_key(|r| Reverse(r.start_epoch_second()));
+    if backfill_contests.len() > MAX_TASK_BACKFILL_CONTESTS {
+        log::info!(
+            "{} stored contests have no problems yet; backfilling {} in this run",
+            backfill_contests.len(),
+            MAX_TASK_BACKFILL_CONTESTS
+        );
+        backfill_contests.truncate(MAX_TASK_BACKFILL_CONTESTS);
+    }
+
+    // Crawl the task list of each new and backfilled contest to record its original problems
+    let task_contest_ids = contests
+        .iter()
+        .map(|c| c.id.clone())
+        .chain(backfill_contests.iter().map(|r| r.contest_id.clone()))
+        .collect::<Vec<_>>();
+    let mut tasks_by_contest = contest_crawler.crawl_tasks(&task_contest_ids).await;
+    let failed_task_crawls = task_contest_ids.len() - tasks_by_contest.len();
+    if failed_task_crawls > 0 {
+        log::warn!(
+            "Failed to fetch tasks for {} contests; they will be retried on the next run",
+            failed_task_crawls
+        );
+    }
+
+    // New contests are stored even without tasks, so the next crawl stops at them
+    let mut contest_dtos = AdtContestDto::from_new_contests(contests.into_iter().map(|c| {
+        let tasks = tasks_by_contest.remove(&c.id).unwrap_or_default();
+        (c, tasks)
+    }));
+    let backfilled_dtos = backfill_contests
+        .into_iter()
+        .filter_map(|r| {
+            tasks_by_contest
+                .remove(&r.contest_id)
+                .map(|tasks| AdtContestDto::from_stored_contest(r, tasks))
+        })
+        .collect::<Vec<_>>();
+    log::info!(
+        "Filled in the problems of {} stored contests",
+        backfilled_dtos.len()
+    );
+    contest_dtos.extend(backfilled_dtos);
+
+    // Pre-register ADT contests that have not ended yet; a failure here does not block the archive sync
+    match contest_crawler.crawl_upcoming().await {
+        Ok(upcoming) => {
+            let new_upcoming = upcoming
+                .into_iter()
+                .filter(|c| !stored_contests.contains_key(&c.id))
+                .filter(|c| contest_dtos.iter().all(|dto| dto.contest_id != c.id))
+                .collect::<Vec<_>>();
+            log::info!("Pre-registering {} upcoming contests", new_upcoming.len());
+            contest_dtos.extend(AdtContestDto::from_upcoming_contests(new_upcoming));
+        }
+        Err(_) => log::warn!("Skipping pre-registration of upcoming contests"),
+    }
+
+    // Keep the submission cursor of contests that were stored before (e.g. pre-registered ones)
+    for dto in &mut contest_dtos {
+        if let Some(stored) = stored_contests.get(&dto.contest_id) {
+            dto.last_fetched_submission_id = stored.last_fetched_submission_id;
+        }
+    }
+
+    let contest_write_records = contest_dtos
         .into_iter()
         .map(|dto| dto.into_record())
         .collect::<Vec<_>>();
@@ -74,12 +181,28 @@ async fn main() {
         "Total contests to write: {} records",
         contest_write_records.len()
     );
+    log::info!(
+        "Contests to write per tier: {}",
+        contest_write_records
+            .iter()
+            .map(|r| &r.contest_id)
+            .collect::<TierCounts>()
+    );
 
-    // Write contests to DynamoDB
-    if let Err(err) = ddb_service.batch_write_items(contest_write_records).await {
-        log::error!("Failed to write contests to DynamoDB: {}", err);
+    // Write contests to the store
+    if let Err(err) = store.batch_write_contests(contest_write_records).await {
+        log::error!("Failed to write contests to the store: {}", err);
         return;
     }
 
+    // Keep the cookie file up to date with any cookies refreshed during the run
+    if let Err(e) = atcoder_client.save_cookies() {
+        log::warn!("Failed to save AtCoder cookies: {}", e);
+    }
+
+    log::info!(
+        "Rate limiter usage: {:?}",
+        atcoder_client.rate_limit_metrics()
+    );
     log::info!("Crawling and writing contests completed successfully");
 }
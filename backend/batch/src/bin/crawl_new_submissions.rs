@@ -1,11 +1,14 @@
+use atcoder_client::SubmissionFilter;
+use atcoder_ids::ContestId;
 use atcoder_problems_adt_sync_batch::{
-    client::{init_atcoder_client, init_ddb_service},
-    constants::{ATCODER_CRAWL_SLEEP_MILLIS, MAX_IN_MEMORY_SUBMISSIONS},
+    client::{init_atcoder_client, init_retry_policy, warn_if_session_expiring},
+    constants::MAX_IN_MEMORY_SUBMISSIONS,
     crawler::SubmissionCrawler,
     dto::AdtContestDto,
+    report::TierCounts,
     service::sync_user_ac_problems_from_submissions,
 };
-use tokio::time::{Duration, sleep};
+use ddb_client::{ContestStore, StoreBackend};
 
 #[tokio::main]
 async fn main() {
@@ -23,12 +26,16 @@ async fn main() {
             return;
         }
     };
+    if let Err(e) = warn_if_session_expiring(&atcoder_client) {
+        log::error!("{}", e);
+        return;
+    }
 
-    // Initialize DynamoDB service
-    let ddb_service = match init_ddb_service().await {
-        Ok(service) => {
-            log::info!("Successfully created DynamoDB service");
-            service
+    // Initialize the store (DynamoDB or SQLite)
+    let store = match StoreBackend::from_env().await {
+        Ok(store) => {
+            log::info!("Successfully opened {} store", store.name());
+            store
         }
         Err(e) => {
             log::error!("{}", e);
@@ -36,33 +43,35 @@ async fn main() {
         }
     };
 
-    // Fetch all contests from DynamoDB
-    let contest_records = match ddb_service.get_contests(None).await {
+    // Fetch all contests from the store
+    let contest_records = match store.get_contests(None).await {
         Ok(records) => {
             log::info!(
-                "Successfully fetched all contests from DynamoDB: {} records",
+                "Successfully fetched all contests from the store: {} records",
                 records.len()
             );
             records
         }
         Err(err) => {
-            log::error!("Failed to fetch all contests from DynamoDB: {}", err);
+            log::error!("Failed to fetch all contests from the store: {}", err);
             return;
         }
     };
 
     // Initialize SubmissionCrawler with AtCoder client
-    let submission_crawler = SubmissionCrawler::new(atcoder_client);
+    let submission_crawler = SubmissionCrawler::new(atcoder_client.clone(), init_retry_policy());
+
+    // Only AC submissions are needed, so let AtCoder filter them server-side
+    let ac_filter = SubmissionFilter::accepted();
 
     let mut new_ac_submissions = vec![];
     let mut update_contests = vec![];
-    let mut batch_start_contest_id: Option<String> = None;
+    let mut batch_start_contest_id: Option<ContestId> = None;
+    let mut rejected_rows = 0;
+    let mut ac_counts = TierCounts::default();
 
     // Crawl submissions for each contest
     for record in &contest_records {
-        // Sleep before processing each contest to maintain consistent intervals
-        sleep(Duration::from_millis(ATCODER_CRAWL_SLEEP_MILLIS)).await;
-
         // Record the start of current write batch
         if batch_start_contest_id.is_none() {
             batch_start_contest_id = Some(record.contest_id.clone());
@@ -70,55 +79,69 @@ async fn main() {
 
         log::debug!("Crawling submissions for contest: {}", record.contest_id);
 
-        // Crawl submissions until the last fetched submission ID
-        let contest_ac_submissions = match submission_crawler
-            .crawl(&record.contest_id, record.last_fetched_submission_id)
+        // Crawl AC submissions until the last fetched submission ID
+        let crawled = match submission_crawler
+            .crawl(
+                &record.contest_id,
+                record.last_fetched_submission_id,
+                &ac_filter,
+            )
             .await
         {
-            Ok(submissions) => submissions
-                .into_iter()
-                .filter(|s| s.is_accepted())
-                .collect::<Vec<_>>(),
+            Ok(crawled) => crawled,
             Err(_) => continue,
         };
-
-        if contest_ac_submissions.is_empty() {
+        rejected_rows += crawled.rejected_rows;
+
+        // The cursor is held behind submissions that are still being judged
+        let next_cursor = crawled.next_cursor;
+        let contest_ac_submissions = crawled
+            .submissions
+            .into_iter()
+            .filter(|s| s.is_accepted())
+            .collect::<Vec<_>>();
+
+        let cursor_moved = next_cursor != record.last_fetched_submission_id;
+        if contest_ac_submissions.is_empty() && !cursor_moved {
             log::debug!("No new AC submissions for contest: {}", record.contest_id);
             continue;
         }
 
-        update_contests.push(
-            AdtContestDto {
-                start_epoch_second: record.start_epoch_second(),
-                contest_id: record.contest_id.clone(),
-                last_fetched_submission_id: contest_ac_submissions.first().map(|s| s.id),
-            }
-            .into_record(),
-        );
+        if cursor_moved {
+            update_contests.push(
+                AdtContestDto {
+                    start_epoch_second: record.start_epoch_second(),
+                    contest_id: record.contest_id.clone(),
+                    last_fetched_submission_id: next_cursor,
+                    problem_ids: record.problem_ids.clone(),
+                }
+                .into_record(),
+            );
+        }
 
+        ac_counts.add(&record.contest_id, contest_ac_submissions.len());
         new_ac_submissions.extend(contest_ac_submissions);
 
         if new_ac_submissions.len() >= MAX_IN_MEMORY_SUBMISSIONS {
             log::warn!(
-                "Reached maximum in-memory submissions limit: {}. Writing to DynamoDB.",
+                "Reached maximum in-memory submissions limit: {}. Writing to the store.",
                 MAX_IN_MEMORY_SUBMISSIONS
             );
 
             log::info!(
-                "Writing {} AC submissions (contests {} to {}) to DynamoDB.",
+                "Writing {} AC submissions (contests {} to {}) to the store.",
                 new_ac_submissions.len(),
                 batch_start_contest_id.as_ref().unwrap(),
                 record.contest_id
             );
 
-            // Write the new AC submissions to DynamoDB
-            if let Err(e) =
-                sync_user_ac_problems_from_submissions(&ddb_service, new_ac_submissions).await
+            // Write the new AC submissions to the store
+            if let Err(e) = sync_user_ac_problems_from_submissions(&store, new_ac_submissions).await
             {
-                log::error!("Failed to write submissions to DynamoDB: {}", e);
+                log::error!("Failed to write submissions to the store: {}", e);
                 return;
             } else {
-                log::info!("Successfully wrote submissions to DynamoDB.");
+                log::info!("Successfully wrote submissions to the store.");
             }
 
             // Clear the in-memory submissions and reset contest range
@@ -127,39 +150,56 @@ async fn main() {
         }
     }
 
-    // Flush remaining AC submissions to DynamoDB
+    // Flush remaining AC submissions to the store
     if !new_ac_submissions.is_empty() {
         log::info!(
-            "Writing remaining {} AC submissions (contests {} to {}) to DynamoDB.",
+            "Writing remaining {} AC submissions (contests {} to {}) to the store.",
             new_ac_submissions.len(),
             batch_start_contest_id.as_ref().unwrap(),
             contest_records.last().map(|c| &c.contest_id).unwrap()
         );
 
-        if let Err(e) =
-            sync_user_ac_problems_from_submissions(&ddb_service, new_ac_submissions).await
-        {
-            log::error!("Failed to write remaining submissions to DynamoDB: {}", e);
+        if let Err(e) = sync_user_ac_problems_from_submissions(&store, new_ac_submissions).await {
+            log::error!("Failed to write remaining submissions to the store: {}", e);
             return;
         } else {
-            log::info!("Successfully wrote remaining submissions to DynamoDB.");
+            log::info!("Successfully wrote remaining submissions to the store.");
         }
     }
 
-    // Write updated contest records to DynamoDB
+    // Write updated contest records to the store
     if !update_contests.is_empty() {
         log::info!(
-            "Updating {} contest records in DynamoDB.",
+            "Updating {} contest records in the store.",
             update_contests.len()
         );
 
-        if let Err(e) = ddb_service.batch_write_items(update_contests).await {
-            log::error!("Failed to update contest records in DynamoDB: {}", e);
+        if let Err(e) = store.batch_write_contests(update_contests).await {
+            log::error!("Failed to update contest records in the store: {}", e);
             return;
         } else {
-            log::info!("Successfully updated contest records in DynamoDB.");
+            log::info!("Successfully updated contest records in the store.");
         }
     }
 
+    log::info!(
+        "New AC submissions per tier: {} (total {})",
+        ac_counts,
+        ac_counts.total()
+    );
+
+    if rejected_rows > 0 {
+        log::warn!("Skipped {} malformed submission rows", rejected_rows);
+    }
+
+    // Keep the cookie file up to date with any cookies refreshed during the run
+    if let Err(e) = atcoder_client.save_cookies() {
+        log::warn!("Failed to save AtCoder cookies: {}", e);
+    }
+
+    log::info!(
+        "Rate limiter usage: {:?}",
+        atcoder_client.rate_limit_metrics()
+    );
     log::info!("Crawling and writing submissions completed successfully");
 }
@@ -1,22 +1,200 @@
-use crate::constants::{ATCODER_SESSION_ENV, DYNAMODB_TABLE_ENV};
-use atcoder_client::AtCoderClient;
-use ddb_client::DdbService;
-use std::env;
+use crate::constants::{
+    ATCODER_CONTEST_CATEGORIES_ENV, ATCODER_COOKIE_FILE_ENV, ATCODER_CRAWL_MAX_RETRIES,
+    ATCODER_CRAWL_RETRY_BASE_MILLIS, ATCODER_CRAWL_RETRY_MAX_MILLIS,
+    ATCODER_DEFAULT_CONTEST_CATEGORIES, ATCODER_DEFAULT_SESSION_EXPIRY_WARN_DAYS,
+    ATCODER_PASSWORD_ENV, ATCODER_RATE_LIMIT_BURST, ATCODER_RECORD_DIR_ENV, ATCODER_REPLAY_DIR_ENV,
+    ATCODER_REQUESTS_PER_SECOND, ATCODER_SESSION_ENV, ATCODER_SESSION_EXPIRY_WARN_DAYS_ENV,
+    ATCODER_USERNAME_ENV,
+};
+use atcoder_client::{AtCoderClient, AtCoderClientBuilder, AtCoderClientError, RetryPolicy};
+use chrono::{DateTime, Utc};
+use std::{env, time::Duration};
 
-/// Initializes the AtCoder client from the environment variable.
+/// Initializes the AtCoder client from the environment variables.
+/// Reuses the session saved in the cookie file if set, then the REVEL_SESSION cookie,
+/// and falls back to password login when both are missing or expired and credentials
+/// are available. The resulting session is saved back to the cookie file.
+/// If a replay directory is set, pages are served from recorded fixtures without logging in.
 pub async fn init_atcoder_client() -> Result<AtCoderClient, String> {
-    let session = env::var(ATCODER_SESSION_ENV)
-        .map_err(|_| format!("Environment variable {} is not set", ATCODER_SESSION_ENV))?;
+    if let Ok(dir) = env::var(ATCODER_REPLAY_DIR_ENV) {
+        log::info!("Replaying AtCoder pages from fixtures in {}", dir);
+        return atcoder_client_builder()
+            .replay_fixtures(dir)
+            .build()
+            .await
+            .map_err(|e| format!("Failed to create AtCoder client: {:?}", e));
+    }
 
-    AtCoderClient::from_revel_session(&session)
+    if let Some(client) = restore_atcoder_client().await? {
+        return Ok(client);
+    }
+
+    let credentials = env::var(ATCODER_USERNAME_ENV)
+        .ok()
+        .zip(env::var(ATCODER_PASSWORD_ENV).ok());
+
+    match env::var(ATCODER_SESSION_ENV) {
+        Ok(session) => match session_client_builder()
+            .revel_session(session)
+            .build()
+            .await
+        {
+            Ok(client) => return Ok(client),
+            Err(AtCoderClientError::InvalidSession) if credentials.is_some() => {
+                log::warn!(
+                    "{} is invalid or expired, falling back to password login",
+                    ATCODER_SESSION_ENV
+                );
+            }
+            Err(e) => return Err(format!("Failed to create AtCoder client: {:?}", e)),
+        },
+        Err(_) if credentials.is_some() => {
+            log::info!(
+                "Environment variable {} is not set, using password login",
+                ATCODER_SESSION_ENV
+            );
+        }
+        Err(_) => {
+            return Err(format!(
+                "Environment variable {} (or {} and {}) is not set",
+                ATCODER_SESSION_ENV, ATCODER_USERNAME_ENV, ATCODER_PASSWORD_ENV
+            ));
+        }
+    }
+
+    let (username, password) = credentials.expect("Credentials should be checked above");
+    session_client_builder()
+        .login(&username, &password)
         .await
-        .map_err(|e| format!("Failed to create AtCoder client: {:?}", e))
+        .map_err(|e| format!("Failed to log in to AtCoder: {:?}", e))
+}
+
+/// Builds the client from the session saved in the cookie file, if one is set and still valid.
+/// Returns `None` so the caller can fall back to the other authentication methods.
+async fn restore_atcoder_client() -> Result<Option<AtCoderClient>, String> {
+    let Ok(path) = env::var(ATCODER_COOKIE_FILE_ENV) else {
+        return Ok(None);
+    };
+
+    match session_client_builder().build().await {
+        Ok(client) if client.revel_session().is_some() => {
+            log::info!("Reusing the AtCoder session saved in {}", path);
+            Ok(Some(client))
+        }
+        Ok(_) => {
+            log::info!("No AtCoder session is saved in {}", path);
+            Ok(None)
+        }
+        Err(AtCoderClientError::InvalidSession) => {
+            log::warn!(
+                "The AtCoder session saved in {} is invalid or expired",
+                path
+            );
+            Ok(None)
+        }
+        Err(e) => Err(format!("Failed to create AtCoder client: {:?}", e)),
+    }
+}
+
+/// Logs a warning if the AtCoder session expires within the configured number of days,
+/// so the REVEL_SESSION cookie or credentials can be renewed before the batch starts failing.
+pub fn warn_if_session_expiring(client: &AtCoderClient) -> Result<(), String> {
+    let warn_days = match env::var(ATCODER_SESSION_EXPIRY_WARN_DAYS_ENV) {
+        Ok(value) => value.trim().parse::<i64>().map_err(|_| {
+            format!(
+                "Environment variable {} is not a number of days: {}",
+                ATCODER_SESSION_EXPIRY_WARN_DAYS_ENV, value
+            )
+        })?,
+        Err(_) => ATCODER_DEFAULT_SESSION_EXPIRY_WARN_DAYS,
+    };
+
+    if let Some(expires_at) = client.session_expires_at()
+        && is_expiring(expires_at, Utc::now(), warn_days)
+    {
+        log::warn!(
+            "AtCoder session expires at {} (within {} days), renew it soon",
+            expires_at,
+            warn_days
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns whether `expires_at` is within `warn_days` days of `now`.
+fn is_expiring(expires_at: DateTime<Utc>, now: DateTime<Utc>, warn_days: i64) -> bool {
+    expires_at - now <= chrono::Duration::days(warn_days)
 }
 
-/// Initializes the DynamoDB service from the environment variable.
-pub async fn init_ddb_service() -> Result<DdbService, String> {
-    let table_name = env::var(DYNAMODB_TABLE_ENV)
-        .map_err(|_| format!("Environment variable {} is not set", DYNAMODB_TABLE_ENV))?;
+/// Returns the retry policy shared by the crawlers.
+pub fn init_retry_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_retries: ATCODER_CRAWL_MAX_RETRIES,
+        base_delay: Duration::from_millis(ATCODER_CRAWL_RETRY_BASE_MILLIS),
+        max_delay: Duration::from_millis(ATCODER_CRAWL_RETRY_MAX_MILLIS),
+        jitter: true,
+    }
+}
+
+/// Returns the contest archive categories to crawl from the environment variable,
+/// defaulting to AtCoder Daily Training only.
+pub fn init_contest_categories() -> Result<Vec<u32>, String> {
+    let Ok(value) = env::var(ATCODER_CONTEST_CATEGORIES_ENV) else {
+        return Ok(ATCODER_DEFAULT_CONTEST_CATEGORIES.to_vec());
+    };
+
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<u32>().map_err(|_| {
+                format!(
+                    "Environment variable {} has an invalid category: {}",
+                    ATCODER_CONTEST_CATEGORIES_ENV, s
+                )
+            })
+        })
+        .collect()
+}
+
+/// Returns a builder with the batch's polite crawling rate limit applied.
+/// Fetched pages are recorded as fixtures if a record directory is set.
+fn atcoder_client_builder() -> AtCoderClientBuilder {
+    let builder =
+        AtCoderClient::builder().rate_limit(ATCODER_REQUESTS_PER_SECOND, ATCODER_RATE_LIMIT_BURST);
+
+    match env::var(ATCODER_RECORD_DIR_ENV) {
+        Ok(dir) => {
+            log::info!("Recording AtCoder pages as fixtures in {}", dir);
+            builder.record_fixtures(dir)
+        }
+        Err(_) => builder,
+    }
+}
+
+/// Returns a builder that persists the cookie jar to the cookie file, if set.
+fn session_client_builder() -> AtCoderClientBuilder {
+    match env::var(ATCODER_COOKIE_FILE_ENV) {
+        Ok(path) => atcoder_client_builder().cookie_file(path),
+        Err(_) => atcoder_client_builder(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_expiring_within_warn_days() {
+        let now = DateTime::parse_from_rfc3339("2025-06-01T00:00:00Z")
+            .unwrap()
+            .to_utc();
 
-    Ok(DdbService::from_env(table_name).await)
+        assert!(is_expiring(now + chrono::Duration::days(3), now, 14));
+        assert!(is_expiring(now + chrono::Duration::days(14), now, 14));
+        assert!(is_expiring(now - chrono::Duration::days(1), now, 14));
+        assert!(!is_expiring(now + chrono::Duration::days(15), now, 14));
+    }
 }
@@ -1,6 +1,19 @@
 pub const ATCODER_SESSION_ENV: &str = "ATCODER_REVEL_SESSION";
-pub const DYNAMODB_TABLE_ENV: &str = "DYNAMODB_TABLE_NAME";
-pub const ATCODER_CRAWL_SLEEP_MILLIS: u64 = 300;
-pub const ATCODER_CRAWL_MAX_RETRIES: usize = 3;
-pub const ATCODER_CRAWL_RETRY_SLEEP_MILLIS: u64 = 60_000;
+pub const ATCODER_USERNAME_ENV: &str = "ATCODER_USERNAME";
+pub const ATCODER_PASSWORD_ENV: &str = "ATCODER_PASSWORD";
+pub const ATCODER_COOKIE_FILE_ENV: &str = "ATCODER_COOKIE_FILE";
+pub const ATCODER_SESSION_EXPIRY_WARN_DAYS_ENV: &str = "ATCODER_SESSION_EXPIRY_WARN_DAYS";
+pub const ATCODER_RECORD_DIR_ENV: &str = "ATCODER_RECORD_DIR";
+pub const ATCODER_REPLAY_DIR_ENV: &str = "ATCODER_REPLAY_DIR";
+pub const ATCODER_CONTEST_CATEGORIES_ENV: &str = "ATCODER_CONTEST_CATEGORIES";
+pub const ATCODER_REQUESTS_PER_SECOND: f64 = 3.0;
+pub const ATCODER_RATE_LIMIT_BURST: u32 = 1;
+pub const ATCODER_CRAWL_MAX_RETRIES: u32 = 3;
+pub const ATCODER_CRAWL_RETRY_BASE_MILLIS: u64 = 5_000;
+pub const ATCODER_CRAWL_RETRY_MAX_MILLIS: u64 = 60_000;
+pub const ATCODER_CRAWL_CONCURRENCY: usize = 4;
+pub const ATCODER_DEFAULT_CONTEST_CATEGORIES: &[u32] = &[60];
+pub const ATCODER_DEFAULT_SESSION_EXPIRY_WARN_DAYS: i64 = 14;
 pub const MAX_IN_MEMORY_SUBMISSIONS: usize = 10_000;
+pub const MAX_CONCURRENT_AC_WRITES: usize = 8;
+pub const MAX_TASK_BACKFILL_CONTESTS: usize = 100;
@@ -2,4 +2,4 @@ mod contest;
 mod submission;
 
 pub use contest::ContestCrawler;
-pub use submission::SubmissionCrawler;
+pub use submission::{CrawledSubmissions, SubmissionCrawler};
@@ -1,43 +1,101 @@
-use crate::constants::ATCODER_CRAWL_SLEEP_MILLIS;
-use atcoder_client::{AtCoderClient, AtCoderClientError, Contest};
-use tokio::time::{Duration, sleep};
+use atcoder_client::{
+    AtCoderClient, AtCoderClientError, Contest, ContestArchiveQuery, ContestTask, RetryPolicy,
+};
+use atcoder_ids::{AdtContestTitle, ContestId};
+use std::collections::{HashMap, HashSet};
 
 /// Provides functionality to crawl the AtCoder contest archive page by page.
 pub struct ContestCrawler {
     client: AtCoderClient,
+    retry_policy: RetryPolicy,
+    categories: Vec<u32>,
 }
 
 impl ContestCrawler {
-    pub fn new(client: AtCoderClient) -> Self {
-        Self { client }
+    /// Creates a crawler for the archive of each of the given contest categories.
+    pub fn new(client: AtCoderClient, retry_policy: RetryPolicy, categories: Vec<u32>) -> Self {
+        Self {
+            client,
+            retry_policy,
+            categories,
+        }
     }
 
-    /// Crawl contest list page by page until `until_contest_id` is found (exclusive).
-    /// If `None`, continue until the end of the archive (determined by an empty or missing page).
+    /// Fetch a contest archive page, retrying according to the retry policy.
+    async fn fetch_contests_with_retry(
+        &self,
+        query: &ContestArchiveQuery,
+        page: u32,
+    ) -> Result<Vec<Contest>, AtCoderClientError> {
+        self.retry_policy
+            .retry(|| self.client.fetch_contest_archive(query, page))
+            .await
+    }
+
+    /// Fetch a contest's task list, retrying according to the retry policy.
+    async fn fetch_tasks_with_retry(
+        &self,
+        contest_id: &ContestId,
+    ) -> Result<Vec<ContestTask>, AtCoderClientError> {
+        self.retry_policy
+            .retry(|| self.client.fetch_contest_tasks(contest_id))
+            .await
+    }
+
+    /// Crawl the archive of each configured category until a contest in `known_contest_ids` is found (exclusive).
+    /// Categories without a known contest are crawled until the end of their archive
+    /// (determined by an empty or missing page).
     pub async fn crawl(
         &self,
-        until_contest_id: Option<&str>,
+        known_contest_ids: &HashSet<ContestId>,
     ) -> Result<Vec<Contest>, AtCoderClientError> {
-        log::debug!("Starting contest crawl");
-        if let Some(id) = until_contest_id {
-            log::debug!("Crawling until contest ID: {}", id);
-        } else {
-            log::debug!("Crawling all contests until the end of archive");
+        let mut all_contests = Vec::new();
+        for &category in &self.categories {
+            let contests = self.crawl_category(category, known_contest_ids).await?;
+            all_contests.extend(contests);
         }
+        Ok(all_contests)
+    }
+
+    /// Crawl the archive of a single category page by page until a known contest is found (exclusive).
+    async fn crawl_category(
+        &self,
+        category: u32,
+        known_contest_ids: &HashSet<ContestId>,
+    ) -> Result<Vec<Contest>, AtCoderClientError> {
+        log::debug!(
+            "Starting contest crawl for category {} with {} known contests",
+            category,
+            known_contest_ids.len()
+        );
 
+        let query = ContestArchiveQuery::category(category);
         let mut all_contests = Vec::new();
         let mut page = 1;
 
         'outer: loop {
-            log::debug!("Fetching contest archive page {}", page);
-            let contests = match self.client.fetch_adt_contests(page).await {
+            log::debug!(
+                "Fetching contest archive page {} of category {}",
+                page,
+                category
+            );
+            let contests = match self.fetch_contests_with_retry(&query, page).await {
                 Ok(c) => c,
                 Err(e) => {
                     if e.is_empty_content() {
-                        log::info!("Reached end of archive at page {}", page);
+                        log::info!(
+                            "Reached end of archive for category {} at page {}",
+                            category,
+                            page
+                        );
                         break;
                     } else {
-                        log::error!("Failed to fetch page {}: {:?}", page, e);
+                        log::error!(
+                            "Failed to fetch page {} of category {}: {}",
+                            page,
+                            category,
+                            e
+                        );
                         return Err(e);
                     }
                 }
@@ -51,24 +109,149 @@ impl ContestCrawler {
             log::debug!("Fetched {} contests from page {}", contests.len(), page);
 
             for contest in contests {
-                if let Some(stop_id) = until_contest_id {
-                    if contest.id == stop_id {
-                        log::debug!("Reached contest ID {}, stopping.", stop_id);
-                        break 'outer;
-                    }
+                if known_contest_ids.contains(&contest.id) {
+                    log::debug!("Reached known contest ID {}, stopping.", contest.id);
+                    break 'outer;
                 }
                 all_contests.push(contest);
             }
 
             page += 1;
-            sleep(Duration::from_millis(ATCODER_CRAWL_SLEEP_MILLIS)).await;
         }
 
         log::debug!(
-            "Crawling completed, total contests fetched: {}",
+            "Crawling completed, total contests fetched for category {}: {}",
+            category,
             all_contests.len()
         );
 
         Ok(all_contests)
     }
+
+    /// Fetch the ADT contests that are running or scheduled to start, retrying according to the retry policy.
+    pub async fn crawl_upcoming(&self) -> Result<Vec<Contest>, AtCoderClientError> {
+        log::debug!("Fetching upcoming ADT contests");
+        let contests = self
+            .retry_policy
+            .retry(|| self.client.fetch_upcoming_adt_contests())
+            .await
+            .inspect_err(|e| log::error!("Failed to fetch upcoming contests: {}", e))?;
+
+        log::debug!("Fetched {} upcoming ADT contests", contests.len());
+        for contest in &contests {
+            warn_on_title_mismatch(contest);
+        }
+        Ok(contests)
+    }
+
+    /// Fetch the task list of each of the given contests.
+    /// A contest whose task list cannot be fetched is logged and left out of the result,
+    /// so one failure does not abort the others and the caller can retry it on a later run.
+    pub async fn crawl_tasks(
+        &self,
+        contest_ids: &[ContestId],
+    ) -> HashMap<ContestId, Vec<ContestTask>> {
+        log::debug!("Starting task crawl for {} contests", contest_ids.len());
+
+        let mut tasks_by_contest = HashMap::with_capacity(contest_ids.len());
+
+        for contest_id in contest_ids {
+            log::debug!("Fetching tasks for contest {}", contest_id);
+            let tasks = match self.fetch_tasks_with_retry(contest_id).await {
+                Ok(t) => t,
+                Err(e) => {
+                    log::warn!(
+                        "Failed to fetch tasks for contest {}, skipping: {}",
+                        contest_id,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            log::debug!("Fetched {} tasks for contest {}", tasks.len(), contest_id);
+            tasks_by_contest.insert(contest_id.clone(), tasks);
+        }
+
+        tasks_by_contest
+    }
+}
+
+/// Warn when an ADT contest title does not describe the tier and date in its ID,
+/// which suggests AtCoder changed its naming scheme.
+fn warn_on_title_mismatch(contest: &Contest) {
+    let Some(adt) = contest.id.adt() else {
+        log::warn!("Unexpected ADT contest ID format: {}", contest.id);
+        return;
+    };
+    match AdtContestTitle::parse(&contest.title) {
+        Ok(title) if adt.matches_title(&title) => {}
+        _ => log::warn!(
+            "ADT contest {} has an unexpected title: {:?}",
+            contest.id,
+            contest.title
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADT_CATEGORY: u32 = 60;
+
+    async fn replay_crawler() -> ContestCrawler {
+        let client = AtCoderClient::builder()
+            .replay_fixtures(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/test_resources/fixtures"
+            ))
+            .build()
+            .await
+            .expect("client should be built");
+        ContestCrawler::new(client, RetryPolicy::none(), vec![ADT_CATEGORY])
+    }
+
+    #[tokio::test]
+    async fn crawl_without_stop_id_fetches_until_end_of_archive() {
+        let crawler = replay_crawler().await;
+        let contests = crawler
+            .crawl(&HashSet::new())
+            .await
+            .expect("crawl should succeed");
+
+        assert_eq!(contests.len(), 50);
+        assert_eq!(contests[0].id, "adt_all_20250522_3");
+    }
+
+    #[tokio::test]
+    async fn crawl_with_stop_id_stops_before_known_contest() {
+        let crawler = replay_crawler().await;
+        let contests = crawler
+            .crawl(&HashSet::from([
+                ContestId::parse("adt_medium_20250522_3").unwrap()
+            ]))
+            .await
+            .expect("crawl should succeed");
+
+        assert_eq!(
+            contests.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(),
+            ["adt_all_20250522_3", "adt_hard_20250522_3"]
+        );
+    }
+
+    #[tokio::test]
+    async fn crawl_tasks_skips_contests_that_fail() {
+        let crawler = replay_crawler().await;
+        let known = ContestId::parse("adt_all_20250522_3").unwrap();
+        // No fixture is recorded for this contest, so fetching its tasks fails
+        let missing = ContestId::parse("adt_all_20990101_1").unwrap();
+
+        let tasks_by_contest = crawler.crawl_tasks(&[missing, known.clone()]).await;
+
+        assert_eq!(tasks_by_contest.len(), 1);
+        let tasks = &tasks_by_contest[&known];
+        assert_eq!(tasks.len(), 9);
+        assert_eq!(tasks[7].problem_id, "abc369_e");
+    }
 }
@@ -1,54 +1,215 @@
-use crate::constants::{
-    ATCODER_CRAWL_MAX_RETRIES, ATCODER_CRAWL_RETRY_SLEEP_MILLIS, ATCODER_CRAWL_SLEEP_MILLIS,
+use crate::constants::ATCODER_CRAWL_CONCURRENCY;
+use atcoder_client::{
+    AtCoderClient, AtCoderClientError, RetryPolicy, ScrapedPage, Submission, SubmissionFilter,
 };
-use atcoder_client::{AtCoderClient, AtCoderClientError, Submission};
-use tokio::time::{Duration, sleep};
+use atcoder_ids::{ContestId, SubmissionId};
+use futures::{StreamExt, stream};
+use std::{collections::VecDeque, ops::RangeInclusive};
+
+/// Submissions collected by a crawl, with the number of malformed rows that were skipped.
+#[derive(Debug, Default)]
+pub struct CrawledSubmissions {
+    pub submissions: Vec<Submission>,
+    pub rejected_rows: usize,
+    /// Submission ID to resume the next crawl from.
+    /// Never newer than a submission that is still being judged, so it is crawled again later.
+    pub next_cursor: Option<SubmissionId>,
+}
+
+impl CrawledSubmissions {
+    /// Returns the oldest crawled submission that is still being judged.
+    fn oldest_pending(&self) -> Option<SubmissionId> {
+        self.submissions
+            .iter()
+            .filter(|s| !s.result.is_final())
+            .map(|s| s.id)
+            .min()
+    }
+
+    /// Returns the newest submission ID that is older than `oldest_pending`,
+    /// falling back to `previous_cursor` if no such submission was crawled.
+    fn resume_cursor(
+        &self,
+        oldest_pending: Option<SubmissionId>,
+        previous_cursor: Option<SubmissionId>,
+    ) -> Option<SubmissionId> {
+        self.submissions
+            .iter()
+            .map(|s| s.id)
+            .filter(|id| oldest_pending.is_none_or(|pending| *id < pending))
+            .max()
+            .or(previous_cursor)
+    }
+}
 
 /// Provides functionality to crawl the AtCoder submission page by page.
 pub struct SubmissionCrawler {
     client: AtCoderClient,
+    retry_policy: RetryPolicy,
 }
 
 impl SubmissionCrawler {
-    pub fn new(client: AtCoderClient) -> Self {
-        Self { client }
+    pub fn new(client: AtCoderClient, retry_policy: RetryPolicy) -> Self {
+        Self {
+            client,
+            retry_policy,
+        }
     }
 
-    /// Fetch submissions for a given contest page with retry logic.
+    /// Fetch submissions for a given contest page, retrying according to the retry policy.
     async fn fetch_submissions_with_retry(
         &self,
-        contest_id: &str,
+        contest_id: &ContestId,
         page: u32,
-    ) -> Result<Vec<Submission>, AtCoderClientError> {
-        for attempt in 1..=ATCODER_CRAWL_MAX_RETRIES {
-            match self.client.fetch_submissions(contest_id, page).await {
-                Ok(submissions) => return Ok(submissions),
-                Err(e) if e.is_retryable() => {
-                    log::warn!(
-                        "Retrying request for contest {} on page {} (attempt {}/{}) after {}ms: {}",
+        filter: &SubmissionFilter,
+    ) -> Result<ScrapedPage<Submission>, AtCoderClientError> {
+        self.retry_policy
+            .retry(|| {
+                self.client
+                    .fetch_submissions_lenient(contest_id, page, filter)
+            })
+            .await
+    }
+
+    /// Fetch the given pages concurrently; the rate limiter still paces the actual requests.
+    /// Results are returned in page order.
+    async fn fetch_pages_concurrently(
+        &self,
+        contest_id: &ContestId,
+        pages: RangeInclusive<u32>,
+        filter: &SubmissionFilter,
+    ) -> VecDeque<Result<ScrapedPage<Submission>, AtCoderClientError>> {
+        log::debug!(
+            "Fetching submission pages {} to {} for contest {} concurrently",
+            pages.start(),
+            pages.end(),
+            contest_id
+        );
+        stream::iter(pages)
+            .map(|page| self.fetch_submissions_with_retry(contest_id, page, filter))
+            .buffered(ATCODER_CRAWL_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Crawl submission list for a given contest page by page until a submission at or older than
+    /// `until_submission_id` is found (exclusive).
+    /// If `None`, continue until the end of submissions (determined by an empty or missing page).
+    /// Submission IDs increase over time, so the crawl stops even if `until_submission_id` itself
+    /// no longer matches `filter`, e.g. after a rejudge.
+    /// Malformed rows are logged and counted instead of failing the crawl.
+    /// Without a stop ID every page is needed, so the pages after the first are fetched concurrently
+    /// when the pagination widget tells the total.
+    ///
+    /// AtCoder never lists submissions being judged under a status filter, so with one the newest
+    /// unfiltered pages are checked for them as well, and `next_cursor` is held behind them.
+    pub async fn crawl(
+        &self,
+        contest_id: &ContestId,
+        until_submission_id: Option<SubmissionId>,
+        filter: &SubmissionFilter,
+    ) -> Result<CrawledSubmissions, AtCoderClientError> {
+        let mut crawled = self
+            .crawl_pages(contest_id, until_submission_id, filter)
+            .await?;
+
+        let mut oldest_pending = crawled.oldest_pending();
+        if filter.status.is_some() {
+            let unfiltered_pending = self
+                .find_pending_submission(contest_id, until_submission_id, filter)
+                .await?;
+            oldest_pending = oldest_pending.into_iter().chain(unfiltered_pending).min();
+        }
+
+        crawled.next_cursor = crawled.resume_cursor(oldest_pending, until_submission_id);
+        if crawled.next_cursor != crawled.submissions.first().map(|s| s.id) {
+            log::info!(
+                "Holding cursor for contest {} at {:?} until judging completes",
+                contest_id,
+                crawled.next_cursor
+            );
+        }
+
+        log::debug!(
+            "Crawling completed, total submissions for contest {} fetched: {} (rejected rows: {})",
+            contest_id,
+            crawled.submissions.len(),
+            crawled.rejected_rows
+        );
+
+        Ok(crawled)
+    }
+
+    /// Find the oldest submission still being judged among the newest submissions, ignoring the
+    /// status filter. Judging runs roughly in submission order, so pages are checked newest first
+    /// only until one contains a final verdict or reaches `until_submission_id`.
+    async fn find_pending_submission(
+        &self,
+        contest_id: &ContestId,
+        until_submission_id: Option<SubmissionId>,
+        filter: &SubmissionFilter,
+    ) -> Result<Option<SubmissionId>, AtCoderClientError> {
+        let unfiltered = SubmissionFilter {
+            status: None,
+            ..filter.clone()
+        };
+        let mut oldest_pending = None;
+        let mut page = 1;
+
+        loop {
+            log::debug!(
+                "Checking unfiltered submission page {} of contest {} for submissions being judged",
+                page,
+                contest_id
+            );
+            let scraped = match self
+                .fetch_submissions_with_retry(contest_id, page, &unfiltered)
+                .await
+            {
+                Ok(s) => s,
+                Err(e) if e.is_empty_content() => break,
+                Err(e) => {
+                    log::error!(
+                        "Failed to check submissions being judged for contest {} on page {}: {}",
                         contest_id,
                         page,
-                        attempt,
-                        ATCODER_CRAWL_MAX_RETRIES,
-                        ATCODER_CRAWL_RETRY_SLEEP_MILLIS,
                         e
                     );
-                    sleep(Duration::from_millis(ATCODER_CRAWL_RETRY_SLEEP_MILLIS)).await;
+                    return Err(e);
+                }
+            };
+
+            let mut settled = scraped.items.is_empty();
+            for submission in &scraped.items {
+                if until_submission_id.is_some_and(|stop_id| submission.id <= stop_id) {
+                    settled = true;
+                    break;
                 }
-                Err(e) => return Err(e),
+                if submission.result.is_final() {
+                    settled = true;
+                } else {
+                    // Rows are listed newest first
+                    oldest_pending = Some(submission.id);
+                }
+            }
+
+            let last_page = scraped.total_pages.is_some_and(|total| page >= total);
+            if settled || last_page {
+                break;
             }
+            page += 1;
         }
 
-        self.client.fetch_submissions(contest_id, page).await
+        Ok(oldest_pending)
     }
 
-    /// Crawl submission list for a given contest page by page until `until_submission_id` is found (exclusive).
-    /// If `None`, continue until the end of submissions (determined by an empty or missing page).
-    pub async fn crawl(
+    /// Crawl the submission pages matching `filter`, newest first, without setting a cursor.
+    async fn crawl_pages(
         &self,
-        contest_id: &str,
-        until_submission_id: Option<u64>,
-    ) -> Result<Vec<Submission>, AtCoderClientError> {
+        contest_id: &ContestId,
+        until_submission_id: Option<SubmissionId>,
+        filter: &SubmissionFilter,
+    ) -> Result<CrawledSubmissions, AtCoderClientError> {
         log::debug!("Starting submission crawl");
         if let Some(id) = until_submission_id {
             log::debug!("Crawling until submission ID: {}", id);
@@ -56,12 +217,20 @@ impl SubmissionCrawler {
             log::debug!("Crawling all submissions until the end of submissions");
         }
 
-        let mut all_submissions = Vec::new();
+        let mut crawled = CrawledSubmissions::default();
         let mut page = 1;
+        let mut prefetched = VecDeque::new();
 
         'outer: loop {
-            log::debug!("Fetching submission page {}", page);
-            let submissions = match self.fetch_submissions_with_retry(contest_id, page).await {
+            let result = match prefetched.pop_front() {
+                Some(result) => result,
+                None => {
+                    log::debug!("Fetching submission page {}", page);
+                    self.fetch_submissions_with_retry(contest_id, page, filter)
+                        .await
+                }
+            };
+            let scraped = match result {
                 Ok(s) => s,
                 Err(e) => {
                     if e.is_empty_content() {
@@ -83,7 +252,19 @@ impl SubmissionCrawler {
                 }
             };
 
-            if submissions.is_empty() {
+            for reason in &scraped.rejected {
+                log::warn!(
+                    "Skipped malformed submission row for contest {} on page {}: {}",
+                    contest_id,
+                    page,
+                    reason
+                );
+            }
+            crawled.rejected_rows += scraped.rejected.len();
+
+            let total_pages = scraped.total_pages;
+            let submissions = scraped.items;
+            if submissions.is_empty() && scraped.rejected.is_empty() {
                 log::warn!("No submissions found on page {}, stopping.", page);
                 break;
             }
@@ -96,25 +277,398 @@ impl SubmissionCrawler {
             );
 
             for submission in submissions {
-                if let Some(stop_id) = until_submission_id {
-                    if submission.id == stop_id {
-                        log::debug!("Reached submission ID {}, stopping.", stop_id);
-                        break 'outer;
-                    }
+                if let Some(stop_id) = until_submission_id
+                    && submission.id <= stop_id
+                {
+                    log::debug!(
+                        "Reached submission ID {} (stop ID {}), stopping.",
+                        submission.id,
+                        stop_id
+                    );
+                    break 'outer;
+                }
+                crawled.submissions.push(submission);
+            }
+
+            if let Some(total) = total_pages {
+                if page >= total {
+                    log::info!(
+                        "Reached last submission page {} for contest {}",
+                        page,
+                        contest_id
+                    );
+                    break;
+                }
+                if page == 1 && until_submission_id.is_none() {
+                    prefetched = self
+                        .fetch_pages_concurrently(contest_id, 2..=total, filter)
+                        .await;
                 }
-                all_submissions.push(submission);
             }
 
             page += 1;
-            sleep(Duration::from_millis(ATCODER_CRAWL_SLEEP_MILLIS)).await;
         }
 
-        log::debug!(
-            "Crawling completed, total submissions for contest {} fetched: {}",
-            contest_id,
-            all_submissions.len()
+        Ok(crawled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atcoder_client::JudgeStatus;
+    use atcoder_ids::{ProblemId, UserId};
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const CONTEST_ID: &str = "adt_all_20250522_3";
+
+    fn contest_id() -> ContestId {
+        ContestId::parse(CONTEST_ID).unwrap()
+    }
+
+    async fn replay_crawler() -> SubmissionCrawler {
+        let client = AtCoderClient::builder()
+            .replay_fixtures(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/test_resources/fixtures"
+            ))
+            .build()
+            .await
+            .expect("client should be built");
+        SubmissionCrawler::new(client, RetryPolicy::none())
+    }
+
+    #[tokio::test]
+    async fn crawl_without_stop_id_fetches_until_end_of_submissions() {
+        let crawler = replay_crawler().await;
+        let submissions = crawler
+            .crawl(&contest_id(), None, &SubmissionFilter::default())
+            .await
+            .expect("crawl should succeed");
+
+        assert_eq!(submissions.submissions.len(), 20);
+        assert_eq!(submissions.submissions[0].id, SubmissionId::new(66203973));
+        assert_eq!(submissions.rejected_rows, 0);
+        assert_eq!(submissions.next_cursor, Some(SubmissionId::new(66203973)));
+    }
+
+    #[tokio::test]
+    async fn crawl_with_stop_id_stops_before_known_submission() {
+        let crawler = replay_crawler().await;
+        let submissions = crawler
+            .crawl(
+                &contest_id(),
+                Some(SubmissionId::new(66194430)),
+                &SubmissionFilter::default(),
+            )
+            .await
+            .expect("crawl should succeed");
+
+        assert_eq!(submissions.submissions.len(), 4);
+    }
+
+    /// Renders a submissions page with the given rows and a numbered pagination widget.
+    fn numbered_submissions_page(page: u32, total: u32, submission_id: u64) -> String {
+        submissions_page(page, total, &[(submission_id, "AC")])
+    }
+
+    fn submissions_page(page: u32, total: u32, rows: &[(u64, &str)]) -> String {
+        let pagination = (1..=total)
+            .map(|p| {
+                let class = if p == page { "active" } else { "" };
+                format!(
+                    r#"<li class="{class}"><a href="/contests/{CONTEST_ID}/submissions?page={p}">{p}</a></li>"#
+                )
+            })
+            .collect::<String>();
+        let rows = rows
+            .iter()
+            .map(|(submission_id, status)| {
+                format!(
+                    r#"<tr>
+                    <td><time>2025-05-27 20:19:54+0900</time></td>
+                    <td><a href="/contests/{CONTEST_ID}/tasks/abc369_e">H</a></td>
+                    <td><a href="/users/test1">test1</a></td>
+                    <td>Rust</td><td>450</td><td>100 Byte</td><td>{status}</td>
+                    <td><a href="/contests/{CONTEST_ID}/submissions/{submission_id}">詳細</a></td>
+                </tr>"#
+                )
+            })
+            .collect::<String>();
+        format!(
+            r#"<ul class="pagination">{pagination}</ul>
+            <table>
+                <thead><tr>
+                    <th>提出日時</th><th>問題</th><th>ユーザ</th><th>言語</th><th>得点</th>
+                    <th>コード長</th><th>結果</th><th></th>
+                </tr></thead>
+                <tbody>{rows}</tbody>
+            </table>"#
+        )
+    }
+
+    #[tokio::test]
+    async fn crawl_without_stop_id_fetches_known_pages_without_probing_past_the_last() {
+        let server = MockServer::start().await;
+        for page in 1..=3 {
+            Mock::given(method("GET"))
+                .and(path(format!("/contests/{CONTEST_ID}/submissions")))
+                .and(query_param("page", page.to_string()))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_string(numbered_submissions_page(
+                        page,
+                        3,
+                        100 - page as u64,
+                    )),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(query_param("page", "4"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .rate_limit(1_000.0, 10)
+            .build()
+            .await
+            .expect("client should be built");
+        let crawled = SubmissionCrawler::new(client, RetryPolicy::none())
+            .crawl(&contest_id(), None, &SubmissionFilter::default())
+            .await
+            .expect("crawl should succeed");
+
+        let ids = crawled
+            .submissions
+            .iter()
+            .map(|s| s.id.get())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![99, 98, 97]);
+        assert_eq!(crawled.next_cursor, Some(SubmissionId::new(99)));
+    }
+
+    #[tokio::test]
+    async fn crawl_stops_at_older_submission_when_stop_id_is_missing() {
+        let server = MockServer::start().await;
+        // The stop ID 98 is not listed, e.g. because it was rejudged out of the filter
+        // Page 1 is also checked without the status filter; its AC row ends that check
+        for (page, submission_id, requests) in [(1, 99, 2), (2, 97, 1)] {
+            Mock::given(method("GET"))
+                .and(path(format!("/contests/{CONTEST_ID}/submissions")))
+                .and(query_param("page", page.to_string()))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_string(numbered_submissions_page(
+                        page,
+                        3,
+                        submission_id,
+                    )),
+                )
+                .expect(requests)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(query_param("page", "3"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .rate_limit(1_000.0, 10)
+            .build()
+            .await
+            .expect("client should be built");
+        let crawled = SubmissionCrawler::new(client, RetryPolicy::none())
+            .crawl(
+                &contest_id(),
+                Some(SubmissionId::new(98)),
+                &SubmissionFilter::accepted(),
+            )
+            .await
+            .expect("crawl should succeed");
+
+        let ids = crawled
+            .submissions
+            .iter()
+            .map(|s| s.id.get())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![99]);
+    }
+
+    #[tokio::test]
+    async fn crawl_with_status_filter_holds_cursor_behind_submissions_being_judged() {
+        let server = MockServer::start().await;
+        // AtCoder drops the WJ row from the AC-filtered listing
+        Mock::given(method("GET"))
+            .and(path(format!("/contests/{CONTEST_ID}/submissions")))
+            .and(query_param("f.Status", "AC"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(submissions_page(
+                1,
+                1,
+                &[(99, "AC"), (96, "AC")],
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/contests/{CONTEST_ID}/submissions")))
+            .and(query_param_is_missing("f.Status"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(submissions_page(
+                1,
+                1,
+                &[(99, "AC"), (98, "WJ"), (97, "WA"), (96, "AC")],
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .rate_limit(1_000.0, 10)
+            .build()
+            .await
+            .expect("client should be built");
+        let crawled = SubmissionCrawler::new(client, RetryPolicy::none())
+            .crawl(
+                &contest_id(),
+                Some(SubmissionId::new(95)),
+                &SubmissionFilter::accepted(),
+            )
+            .await
+            .expect("crawl should succeed");
+
+        let ids = crawled
+            .submissions
+            .iter()
+            .map(|s| s.id.get())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![99, 96]);
+        assert_eq!(crawled.next_cursor, Some(SubmissionId::new(96)));
+    }
+
+    #[tokio::test]
+    async fn crawl_with_status_filter_checks_only_newest_unfiltered_page() {
+        let server = MockServer::start().await;
+        for (page, submission_id) in [(1, 99), (2, 97), (3, 95)] {
+            Mock::given(method("GET"))
+                .and(path(format!("/contests/{CONTEST_ID}/submissions")))
+                .and(query_param("f.Status", "AC"))
+                .and(query_param("page", page.to_string()))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_string(numbered_submissions_page(
+                        page,
+                        3,
+                        submission_id,
+                    )),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        // The unfiltered listing has many pages, but the first already has a final verdict
+        Mock::given(method("GET"))
+            .and(path(format!("/contests/{CONTEST_ID}/submissions")))
+            .and(query_param_is_missing("f.Status"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(submissions_page(
+                1,
+                10,
+                &[(100, "WJ"), (99, "AC")],
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/contests/{CONTEST_ID}/submissions")))
+            .and(query_param_is_missing("f.Status"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = AtCoderClient::builder()
+            .base_url(server.uri())
+            .rate_limit(1_000.0, 10)
+            .build()
+            .await
+            .expect("client should be built");
+        let crawled = SubmissionCrawler::new(client, RetryPolicy::none())
+            .crawl(&contest_id(), None, &SubmissionFilter::accepted())
+            .await
+            .expect("crawl should succeed");
+
+        assert_eq!(crawled.submissions.len(), 3);
+        assert_eq!(crawled.next_cursor, Some(SubmissionId::new(99)));
+    }
+
+    fn submission(id: u64, result: JudgeStatus) -> Submission {
+        Submission {
+            id: SubmissionId::new(id),
+            epoch_second: 0,
+            problem_id: ProblemId::parse("abc369_e").unwrap(),
+            contest_id: contest_id(),
+            user_id: UserId::parse("test1").unwrap(),
+            language: "Rust".to_string(),
+            point: 0.0,
+            length: 0,
+            result,
+            execution_time: None,
+            memory_kb: None,
+        }
+    }
+
+    #[test]
+    fn resume_cursor_stays_behind_submissions_being_judged() {
+        let crawled = CrawledSubmissions {
+            submissions: vec![
+                submission(5, JudgeStatus::Accepted),
+                submission(
+                    4,
+                    JudgeStatus::Judging {
+                        judged: 3,
+                        total: 20,
+                    },
+                ),
+                submission(3, JudgeStatus::Accepted),
+                submission(2, JudgeStatus::WaitingForJudge),
+                submission(1, JudgeStatus::WrongAnswer),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(crawled.oldest_pending(), Some(SubmissionId::new(2)));
+        assert_eq!(
+            crawled.resume_cursor(crawled.oldest_pending(), None),
+            Some(SubmissionId::new(1))
         );
+    }
 
-        Ok(all_submissions)
+    #[test]
+    fn resume_cursor_keeps_previous_cursor_when_oldest_is_being_judged() {
+        let crawled = CrawledSubmissions {
+            submissions: vec![
+                submission(5, JudgeStatus::Accepted),
+                submission(4, JudgeStatus::WaitingForRejudge),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            crawled.resume_cursor(crawled.oldest_pending(), Some(SubmissionId::new(3))),
+            Some(SubmissionId::new(3))
+        );
+        assert_eq!(
+            CrawledSubmissions::default().resume_cursor(None, Some(SubmissionId::new(3))),
+            Some(SubmissionId::new(3))
+        );
     }
 }
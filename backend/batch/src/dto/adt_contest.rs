@@ -1,12 +1,14 @@
-use atcoder_client::Contest;
+use atcoder_client::{Contest, ContestTask};
+use atcoder_ids::{ContestId, ProblemId, SubmissionId};
 use ddb_client::AdtContestRecord;
 
 /// Data Transfer Object (DTO) for adt contests.
 #[derive(Debug, Clone)]
 pub struct AdtContestDto {
     pub start_epoch_second: u64,
-    pub contest_id: String,
-    pub last_fetched_submission_id: Option<u64>,
+    pub contest_id: ContestId,
+    pub last_fetched_submission_id: Option<SubmissionId>,
+    pub problem_ids: Vec<ProblemId>,
 }
 
 impl AdtContestDto {
@@ -27,20 +29,51 @@ impl AdtContestDto {
             sk,
             contest_id: self.contest_id,
             last_fetched_submission_id: self.last_fetched_submission_id,
+            problem_ids: self.problem_ids,
         }
     }
 
-    /// Converts a list of crawled contests into DTOs for DynamoDB writing.
+    /// Converts a list of crawled contests and their tasks into DTOs for DynamoDB writing.
     pub fn from_new_contests<I>(new_contests: I) -> Vec<Self>
     where
-        I: IntoIterator<Item = Contest>,
+        I: IntoIterator<Item = (Contest, Vec<ContestTask>)>,
     {
         new_contests
+            .into_iter()
+            .map(|(c, tasks)| Self {
+                start_epoch_second: c.start_epoch_second,
+                contest_id: c.id,
+                last_fetched_submission_id: None,
+                problem_ids: tasks.into_iter().map(|t| t.problem_id).collect(),
+            })
+            .collect()
+    }
+
+    /// Converts a stored contest without problem IDs and its crawled tasks into a DTO
+    /// that fills them in, keeping the stored submission cursor.
+    pub fn from_stored_contest(record: &AdtContestRecord, tasks: Vec<ContestTask>) -> Self {
+        Self {
+            start_epoch_second: record.start_epoch_second(),
+            contest_id: record.contest_id.clone(),
+            last_fetched_submission_id: record.last_fetched_submission_id,
+            problem_ids: tasks.into_iter().map(|t| t.problem_id).collect(),
+        }
+    }
+
+    /// Converts contests that have not ended yet into DTOs for pre-registration.
+    /// Their task lists are not public before the start, so `problem_ids` is left empty
+    /// and filled in by the task backfill once the contest has started.
+    pub fn from_upcoming_contests<I>(upcoming_contests: I) -> Vec<Self>
+    where
+        I: IntoIterator<Item = Contest>,
+    {
+        upcoming_contests
             .into_iter()
             .map(|c| Self {
                 start_epoch_second: c.start_epoch_second,
                 contest_id: c.id,
                 last_fetched_submission_id: None,
+                problem_ids: Vec::new(),
             })
             .collect()
     }
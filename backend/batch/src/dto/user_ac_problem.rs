@@ -1,32 +1,29 @@
 use atcoder_client::Submission;
-use ddb_client::UserAcProblemRecord;
-use std::collections::{BTreeMap, BTreeSet};
+use atcoder_ids::{ProblemId, UserId};
+use ddb_client::{FirstAc, UserAcProblemRecord};
+use std::collections::{BTreeMap, btree_map::Entry};
 
 /// Data Transfer Object (DTO) for user accepted problems.
 #[derive(Debug, Clone)]
 pub struct UserAcProblemDto {
-    pub user_id: String,
-    pub ac_problems: BTreeSet<String>,
+    pub user_id: UserId,
+    /// AC problems mapped to the earliest of the given AC submissions.
+    pub ac_problems: BTreeMap<ProblemId, FirstAc>,
 }
 
 impl UserAcProblemDto {
+    /// Converts this DTO into the AC problems to add to the user's stored record.
+    pub fn into_ac_problems(self) -> BTreeMap<ProblemId, Option<FirstAc>> {
+        self.ac_problems
+            .into_iter()
+            .map(|(problem_id, first_ac)| (problem_id, Some(first_ac)))
+            .collect()
+    }
+
     /// Converts this DTO into an UserAcProblemRecord for DynamoDB storage.
     pub fn into_record(self) -> UserAcProblemRecord {
-        let pk = UserAcProblemRecord::pk_attr(&self.user_id)
-            .as_s()
-            .expect("PK must be a string")
-            .to_owned();
-
-        let sk = UserAcProblemRecord::sk_attr()
-            .as_s()
-            .expect("SK must be a string")
-            .to_owned();
-
-        UserAcProblemRecord {
-            pk,
-            sk,
-            ac_problems: self.ac_problems.into_iter().collect(),
-        }
+        let user_id = self.user_id.clone();
+        UserAcProblemRecord::new(&user_id, self.into_ac_problems())
     }
 
     /// Converts a list of crawled AC submissions into DTOs for DynamoDB writing.
@@ -34,13 +31,26 @@ impl UserAcProblemDto {
     where
         I: IntoIterator<Item = Submission>,
     {
-        let mut user_to_problems: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut user_to_problems: BTreeMap<UserId, BTreeMap<ProblemId, FirstAc>> = BTreeMap::new();
 
         for submission in new_ac_submissions {
-            user_to_problems
-                .entry(submission.user_id)
-                .or_default()
-                .insert(submission.problem_id);
+            let first_ac = FirstAc {
+                epoch_second: submission.epoch_second,
+                contest_id: submission.contest_id,
+                submission_id: submission.id,
+            };
+            let problems = user_to_problems.entry(submission.user_id).or_default();
+            match problems.entry(submission.problem_id) {
+                Entry::Vacant(entry) => {
+                    entry.insert(first_ac);
+                }
+                Entry::Occupied(mut entry) => {
+                    // Submission IDs increase over time, so the smaller ID is the earlier AC
+                    if first_ac.submission_id < entry.get().submission_id {
+                        entry.insert(first_ac);
+                    }
+                }
+            }
         }
 
         user_to_problems
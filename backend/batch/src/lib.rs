@@ -2,4 +2,5 @@ pub mod client;
 pub mod constants;
 pub mod crawler;
 pub mod dto;
+pub mod report;
 pub mod service;
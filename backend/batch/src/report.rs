@@ -0,0 +1,74 @@
+use atcoder_ids::{AdtTier, ContestId};
+use std::{collections::BTreeMap, fmt};
+
+/// Counts of items (contests, submissions, ...) per ADT tier for log summaries.
+/// Contests whose ID is not a well-formed ADT ID are counted as "other".
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TierCounts {
+    tiers: BTreeMap<AdtTier, usize>,
+    other: usize,
+}
+
+impl TierCounts {
+    /// Add `count` items belonging to the given contest.
+    pub fn add(&mut self, contest_id: &ContestId, count: usize) {
+        match contest_id.adt() {
+            Some(adt) => *self.tiers.entry(adt.tier).or_default() += count,
+            None => self.other += count,
+        }
+    }
+
+    pub fn get(&self, tier: AdtTier) -> usize {
+        self.tiers.get(&tier).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.tiers.values().sum::<usize>() + self.other
+    }
+}
+
+impl<'a> FromIterator<&'a ContestId> for TierCounts {
+    fn from_iter<I: IntoIterator<Item = &'a ContestId>>(iter: I) -> Self {
+        let mut counts = Self::default();
+        for contest_id in iter {
+            counts.add(contest_id, 1);
+        }
+        counts
+    }
+}
+
+impl fmt::Display for TierCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for tier in AdtTier::TIERS {
+            write!(f, "{}={}, ", tier, self.get(tier))?;
+        }
+        write!(f, "other={}", self.other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_items_per_tier() {
+        let ids = [
+            "adt_easy_20250522_1",
+            "adt_easy_20250522_2",
+            "adt_all_20250522_1",
+            "abc407",
+        ]
+        .map(|id| ContestId::parse(id).unwrap());
+
+        let mut counts = ids.iter().collect::<TierCounts>();
+        counts.add(&ids[2], 3);
+
+        assert_eq!(counts.get(AdtTier::Easy), 2);
+        assert_eq!(counts.get(AdtTier::All), 4);
+        assert_eq!(counts.total(), 7);
+        assert_eq!(
+            counts.to_string(),
+            "easy=2, medium=0, hard=0, all=4, other=1"
+        );
+    }
+}
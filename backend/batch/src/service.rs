@@ -1,40 +1,101 @@
+use crate::constants::MAX_CONCURRENT_AC_WRITES;
 use crate::dto::UserAcProblemDto;
 use atcoder_client::Submission;
-use ddb_client::{DdbError, DdbService};
-use std::collections::HashMap;
+use ddb_client::{AcStore, DdbError};
+use futures::{StreamExt, TryStreamExt, stream};
 
-/// Updates user AC problem records in DynamoDB based on new submissions.
-pub async fn sync_user_ac_problems_from_submissions(
-    ddb_service: &DdbService,
+/// Adds the AC problems of new submissions to the users' records in the store.
+/// Each user is updated with `AcStore::add_ac_problems`, so overlapping runs or manual
+/// backfills cannot drop each other's ACs.
+pub async fn sync_user_ac_problems_from_submissions<S: AcStore + Sync>(
+    store: &S,
     submissions: Vec<Submission>,
 ) -> Result<(), DdbError> {
     if submissions.is_empty() {
         return Ok(());
     }
 
-    // Group new AC problems by user
-    let mut new_map = HashMap::new();
-    for dto in UserAcProblemDto::from_new_ac_submissions(submissions) {
-        let record = dto.into_record();
-        new_map.insert(record.user_id(), record);
-    }
-
-    let user_ids: Vec<String> = new_map.keys().cloned().collect();
+    // Group new AC problems by user and add them to each user's record
+    stream::iter(UserAcProblemDto::from_new_ac_submissions(submissions))
+        .map(Ok)
+        .try_for_each_concurrent(MAX_CONCURRENT_AC_WRITES, |dto| async move {
+            let user_id = dto.user_id.clone();
+            store
+                .add_ac_problems(&user_id, dto.into_ac_problems())
+                .await
+        })
+        .await
+}
 
-    // Load existing records from DynamoDB
-    let existing_map = ddb_service.batch_get_user_ac_problems(user_ids).await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atcoder_client::JudgeStatus;
+    use atcoder_ids::{ContestId, ProblemId, SubmissionId, UserId};
+    use ddb_client::MemoryStore;
 
-    // Merge new and existing problems
-    let mut merged = Vec::with_capacity(new_map.len());
-    for (user_id, mut new_record) in new_map {
-        if let Some(existing) = existing_map.get(&user_id) {
-            new_record.merge_ac_problems_from(existing);
+    fn ac_submission(id: u64, user_id: &str, problem_id: &str) -> Submission {
+        Submission {
+            id: SubmissionId::new(id),
+            epoch_second: 1_747_913_400 + id,
+            problem_id: ProblemId::parse(problem_id).unwrap(),
+            contest_id: ContestId::parse("adt_all_20250522_3").unwrap(),
+            user_id: UserId::parse(user_id).unwrap(),
+            language: "C++ 20 (gcc 12.2)".to_string(),
+            point: 100.0,
+            length: 100,
+            result: JudgeStatus::Accepted,
+            execution_time: Some(1),
+            memory_kb: Some(3600),
         }
-        merged.push(new_record);
     }
 
-    // Write updated records
-    ddb_service.batch_write_items(merged).await?;
+    /// Stored AC problems of the user with the submission ID of their first AC.
+    async fn first_acs(store: &MemoryStore, user_id: &str) -> Vec<(String, u64)> {
+        store
+            .get_user_ac_problems(&UserId::parse(user_id).unwrap())
+            .await
+            .unwrap()
+            .ac_problems
+            .into_iter()
+            .map(|(problem_id, first_ac)| {
+                (problem_id.into(), first_ac.unwrap().submission_id.get())
+            })
+            .collect()
+    }
 
-    Ok(())
+    #[tokio::test]
+    async fn sync_merges_new_problems_into_stored_records_keeping_first_acs() {
+        let store = MemoryStore::new();
+        sync_user_ac_problems_from_submissions(
+            &store,
+            vec![
+                ac_submission(1, "alice", "abc407_b"),
+                ac_submission(2, "bob", "abc407_a"),
+            ],
+        )
+        .await
+        .unwrap();
+
+        sync_user_ac_problems_from_submissions(
+            &store,
+            vec![
+                ac_submission(4, "alice", "abc407_a"),
+                ac_submission(3, "alice", "abc407_a"),
+                ac_submission(5, "alice", "abc407_b"),
+            ],
+        )
+        .await
+        .unwrap();
+
+        // The earliest AC of each problem is kept across batches
+        assert_eq!(
+            first_acs(&store, "alice").await,
+            [("abc407_a".to_string(), 3), ("abc407_b".to_string(), 1)]
+        );
+        assert_eq!(
+            first_acs(&store, "bob").await,
+            [("abc407_a".to_string(), 2)]
+        );
+    }
 }
@@ -0,0 +1,25 @@
+-- AC problems per user, one row per (user, problem)
+CREATE TABLE user_ac_problems (
+    user_id TEXT NOT NULL,
+    problem_id TEXT NOT NULL,
+    PRIMARY KEY (user_id, problem_id)
+) WITHOUT ROWID;
+
+-- ADT contests keyed like the DynamoDB table, with the submission crawl cursor
+CREATE TABLE adt_contests (
+    pk TEXT NOT NULL,
+    sk TEXT NOT NULL,
+    contest_id TEXT NOT NULL,
+    last_fetched_submission_id INTEGER,
+    PRIMARY KEY (pk, sk)
+) WITHOUT ROWID;
+
+-- Original problems of each ADT contest, in task order
+CREATE TABLE adt_contest_problems (
+    pk TEXT NOT NULL,
+    sk TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    problem_id TEXT NOT NULL,
+    PRIMARY KEY (pk, sk, position),
+    FOREIGN KEY (pk, sk) REFERENCES adt_contests (pk, sk) ON DELETE CASCADE
+) WITHOUT ROWID;
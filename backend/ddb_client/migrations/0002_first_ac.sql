@@ -0,0 +1,4 @@
+-- First AC of each problem; NULL for problems stored before first ACs were recorded
+ALTER TABLE user_ac_problems ADD COLUMN first_ac_epoch_second INTEGER;
+ALTER TABLE user_ac_problems ADD COLUMN first_ac_contest_id TEXT;
+ALTER TABLE user_ac_problems ADD COLUMN first_ac_submission_id INTEGER;
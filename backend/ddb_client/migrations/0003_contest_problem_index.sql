@@ -0,0 +1,2 @@
+-- Look up the ADT contests that included a problem
+CREATE INDEX adt_contest_problems_problem_id ON adt_contest_problems (problem_id);
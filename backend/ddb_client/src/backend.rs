@@ -0,0 +1,137 @@
+#[cfg(feature = "sqlite")]
+use crate::constants::SQLITE_DATABASE_PATH_ENV;
+use crate::constants::{DYNAMODB_TABLE_ENV, STORE_BACKEND_ENV};
+use crate::error::DdbError;
+use crate::models::{AdtContestRecord, FirstAc, UserAcProblemRecord};
+use crate::service::DdbService;
+#[cfg(feature = "sqlite")]
+use crate::sqlite::SqliteStore;
+use crate::store::{AcStore, ContestStore};
+use atcoder_ids::{ProblemId, UserId};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+
+/// Store selected at runtime from configuration.
+#[derive(Clone)]
+pub enum StoreBackend {
+    DynamoDb(DdbService),
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteStore),
+}
+
+impl StoreBackend {
+    /// Use the given DynamoDB table, loading AWS SDK config from the environment.
+    pub async fn dynamodb(table_name: impl Into<String>) -> Self {
+        Self::DynamoDb(DdbService::from_env(table_name).await)
+    }
+
+    /// Open (or create) the SQLite database at `path` and apply pending migrations.
+    #[cfg(feature = "sqlite")]
+    pub fn sqlite(path: impl AsRef<std::path::Path>) -> Result<Self, DdbError> {
+        SqliteStore::open(path).map(Self::Sqlite)
+    }
+
+    /// Open the store selected by the environment variables.
+    /// Uses the DynamoDB table in `DYNAMODB_TABLE_NAME` unless `STORE_BACKEND` is set to "sqlite",
+    /// in which case the SQLite database at `SQLITE_DATABASE_PATH` is opened.
+    pub async fn from_env() -> Result<Self, DdbError> {
+        let backend = env::var(STORE_BACKEND_ENV).unwrap_or_else(|_| "dynamodb".to_string());
+
+        match backend.as_str() {
+            "dynamodb" => Ok(Self::dynamodb(required_env(DYNAMODB_TABLE_ENV)?).await),
+            #[cfg(feature = "sqlite")]
+            "sqlite" => Self::sqlite(required_env(SQLITE_DATABASE_PATH_ENV)?),
+            #[cfg(not(feature = "sqlite"))]
+            "sqlite" => Err(DdbError::InvalidConfig(format!(
+                "{} is \"sqlite\" but the `sqlite` feature is not enabled",
+                STORE_BACKEND_ENV
+            ))),
+            other => Err(DdbError::InvalidConfig(format!(
+                "{} is {:?} (expected \"dynamodb\" or \"sqlite\")",
+                STORE_BACKEND_ENV, other
+            ))),
+        }
+    }
+
+    /// Short name of the backend for logging.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DynamoDb(_) => "DynamoDB",
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(_) => "SQLite",
+        }
+    }
+}
+
+/// Read an environment variable the selected backend needs.
+fn required_env(name: &str) -> Result<String, DdbError> {
+    env::var(name).map_err(|_| DdbError::InvalidConfig(format!("{} is not set", name)))
+}
+
+impl AcStore for StoreBackend {
+    async fn get_user_ac_problems(
+        &self,
+        user_id: &UserId,
+    ) -> Result<UserAcProblemRecord, DdbError> {
+        match self {
+            Self::DynamoDb(store) => store.get_user_ac_problems(user_id).await,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => store.get_user_ac_problems(user_id).await,
+        }
+    }
+
+    async fn batch_get_user_ac_problems(
+        &self,
+        user_ids: Vec<UserId>,
+    ) -> Result<HashMap<UserId, UserAcProblemRecord>, DdbError> {
+        match self {
+            Self::DynamoDb(store) => store.batch_get_user_ac_problems(user_ids).await,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => store.batch_get_user_ac_problems(user_ids).await,
+        }
+    }
+
+    async fn add_ac_problems(
+        &self,
+        user_id: &UserId,
+        problems: BTreeMap<ProblemId, Option<FirstAc>>,
+    ) -> Result<(), DdbError> {
+        match self {
+            Self::DynamoDb(store) => store.add_ac_problems(user_id, problems).await,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => store.add_ac_problems(user_id, problems).await,
+        }
+    }
+}
+
+impl ContestStore for StoreBackend {
+    async fn get_contests(
+        &self,
+        max_items: Option<usize>,
+    ) -> Result<Vec<AdtContestRecord>, DdbError> {
+        match self {
+            Self::DynamoDb(store) => store.get_contests(max_items).await,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => store.get_contests(max_items).await,
+        }
+    }
+
+    async fn get_contests_by_problem(
+        &self,
+        problem_id: &ProblemId,
+    ) -> Result<Vec<AdtContestRecord>, DdbError> {
+        match self {
+            Self::DynamoDb(store) => store.get_contests_by_problem(problem_id).await,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => store.get_contests_by_problem(problem_id).await,
+        }
+    }
+
+    async fn batch_write_contests(&self, records: Vec<AdtContestRecord>) -> Result<(), DdbError> {
+        match self {
+            Self::DynamoDb(store) => store.batch_write_contests(records).await,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => store.batch_write_contests(records).await,
+        }
+    }
+}
@@ -2,3 +2,11 @@ pub const MAX_BATCH_GET: usize = 100;
 pub const MAX_BATCH_WRITE: usize = 25;
 pub const MAX_RETRIES: usize = 5;
 pub const BASE_BACKOFF_MILLIS: u64 = 100;
+pub const MAX_BACKOFF_MILLIS: u64 = 2_000;
+pub const MAX_CONFLICT_RETRIES: usize = 16;
+pub const MAX_TRANSACT_ITEMS: usize = 100;
+pub const MAX_TRANSACT_BYTES: usize = 4 * 1024 * 1024;
+pub const STORE_BACKEND_ENV: &str = "STORE_BACKEND";
+pub const DYNAMODB_TABLE_ENV: &str = "DYNAMODB_TABLE_NAME";
+#[cfg(feature = "sqlite")]
+pub const SQLITE_DATABASE_PATH_ENV: &str = "SQLITE_DATABASE_PATH";
@@ -23,6 +23,23 @@ pub enum DdbError {
     /// Unprocessed items exceeded retry limit.
     #[error("Unprocessed items exceeded retry limit")]
     UnprocessedItemsExceeded,
+
+    /// A conditional write kept losing to concurrent updates of the same item.
+    #[error("Conditional write conflicts exceeded retry limit")]
+    ConditionalWriteConflict,
+
+    /// A transaction would exceed DynamoDB's limits on its number of actions or total size.
+    #[error("Transaction too large: {actions} actions, {bytes} bytes")]
+    TransactionTooLarge { actions: usize, bytes: usize },
+
+    /// The store configuration in the environment is missing or invalid.
+    #[error("Invalid store configuration: {0}")]
+    InvalidConfig(String),
+
+    /// SQLite error in the SQLite-backed store.
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    SqliteError(#[source] rusqlite::Error),
 }
 
 impl DdbError {
@@ -66,6 +83,23 @@ impl From<BatchWriteItemError> for DdbError {
     }
 }
 
+type PutItemError =
+    aws_sdk_dynamodb::error::SdkError<aws_sdk_dynamodb::operation::put_item::PutItemError>;
+impl From<PutItemError> for DdbError {
+    fn from(source: PutItemError) -> Self {
+        DdbError::AwsSdkError(Box::new(source.into()))
+    }
+}
+
+type TransactWriteItemsError = aws_sdk_dynamodb::error::SdkError<
+    aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError,
+>;
+impl From<TransactWriteItemsError> for DdbError {
+    fn from(source: TransactWriteItemsError) -> Self {
+        DdbError::AwsSdkError(Box::new(source.into()))
+    }
+}
+
 // === External (non-SDK) error conversions ===
 impl From<serde_dynamo::Error> for DdbError {
     fn from(err: serde_dynamo::Error) -> Self {
@@ -78,3 +112,10 @@ impl From<aws_sdk_dynamodb::error::BuildError> for DdbError {
         DdbError::AwsBuildError(err.to_string())
     }
 }
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for DdbError {
+    fn from(err: rusqlite::Error) -> Self {
+        DdbError::SqliteError(err)
+    }
+}
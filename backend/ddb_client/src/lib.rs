@@ -1,9 +1,21 @@
+mod backend;
 mod constants;
 mod error;
+#[cfg(feature = "memory")]
+mod memory;
 mod models;
 mod operations;
 mod service;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod store;
 
+pub use backend::StoreBackend;
 pub use error::DdbError;
-pub use models::{AdtContestRecord, UserAcProblemRecord};
+#[cfg(feature = "memory")]
+pub use memory::MemoryStore;
+pub use models::{AdtContestRecord, FirstAc, UserAcProblemRecord};
 pub use service::DdbService;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+pub use store::{AcStore, ContestStore};
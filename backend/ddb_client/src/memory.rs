@@ -0,0 +1,219 @@
+use crate::error::DdbError;
+use crate::models::{AdtContestRecord, FirstAc, UserAcProblemRecord};
+use crate::store::{AcStore, ContestStore};
+use atcoder_ids::{ProblemId, UserId};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// In-memory store with the same key layout as the DynamoDB table, for tests and local runs.
+/// Clones share the same data.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore {
+    tables: Arc<Mutex<Tables>>,
+}
+
+#[derive(Debug, Default)]
+struct Tables {
+    /// User AC problem records keyed by PK.
+    user_ac_problems: HashMap<String, UserAcProblemRecord>,
+    /// Contest records keyed by (PK, SK), iterated in reverse to match the DynamoDB query order.
+    contests: BTreeMap<(String, String), AdtContestRecord>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn tables(&self) -> MutexGuard<'_, Tables> {
+        self.tables.lock().expect("memory store lock poisoned")
+    }
+}
+
+impl AcStore for MemoryStore {
+    async fn get_user_ac_problems(
+        &self,
+        user_id: &UserId,
+    ) -> Result<UserAcProblemRecord, DdbError> {
+        self.tables()
+            .user_ac_problems
+            .get(&UserAcProblemRecord::pk(user_id))
+            .cloned()
+            .ok_or(DdbError::NotFound)
+    }
+
+    async fn batch_get_user_ac_problems(
+        &self,
+        user_ids: Vec<UserId>,
+    ) -> Result<HashMap<UserId, UserAcProblemRecord>, DdbError> {
+        let tables = self.tables();
+        Ok(user_ids
+            .into_iter()
+            .filter_map(|user_id| {
+                let record = tables
+                    .user_ac_problems
+                    .get(&UserAcProblemRecord::pk(&user_id))?;
+                Some((user_id, record.clone()))
+            })
+            .collect())
+    }
+
+    async fn add_ac_problems(
+        &self,
+        user_id: &UserId,
+        problems: BTreeMap<ProblemId, Option<FirstAc>>,
+    ) -> Result<(), DdbError> {
+        let mut tables = self.tables();
+        let mut record = UserAcProblemRecord::new(user_id, problems);
+        if let Some(current) = tables.user_ac_problems.get(&record.pk) {
+            record.merge_ac_problems_from(current);
+            record.version = current.version + 1;
+        } else {
+            record.version = 1;
+        }
+        tables.user_ac_problems.insert(record.pk.clone(), record);
+        Ok(())
+    }
+}
+
+impl ContestStore for MemoryStore {
+    async fn get_contests(
+        &self,
+        max_items: Option<usize>,
+    ) -> Result<Vec<AdtContestRecord>, DdbError> {
+        let tables = self.tables();
+        Ok(tables
+            .contests
+            .values()
+            .rev()
+            .take(max_items.unwrap_or(usize::MAX))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_contests_by_problem(
+        &self,
+        problem_id: &ProblemId,
+    ) -> Result<Vec<AdtContestRecord>, DdbError> {
+        let tables = self.tables();
+        Ok(tables
+            .contests
+            .values()
+            .rev()
+            .filter(|contest| contest.contains_problem(problem_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn batch_write_contests(&self, records: Vec<AdtContestRecord>) -> Result<(), DdbError> {
+        let mut tables = self.tables();
+        for record in records {
+            tables
+                .contests
+                .insert((record.pk.clone(), record.sk.clone()), record);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atcoder_ids::{ContestId, ProblemId};
+
+    fn contest(start_epoch_second: u64, contest_id: &str) -> AdtContestRecord {
+        let contest_id = ContestId::parse(contest_id).unwrap();
+        AdtContestRecord {
+            pk: AdtContestRecord::pk_attr_from_epoch(start_epoch_second)
+                .as_s()
+                .unwrap()
+                .clone(),
+            sk: AdtContestRecord::sk_attr(start_epoch_second, &contest_id)
+                .as_s()
+                .unwrap()
+                .clone(),
+            contest_id,
+            last_fetched_submission_id: None,
+            problem_ids: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn contests_are_returned_newest_first() {
+        let store = MemoryStore::new();
+        store
+            .batch_write_contests(vec![
+                contest(1_747_913_400, "adt_all_20250522_3"),
+                contest(1_749_000_000, "adt_easy_20250604_1"),
+                contest(1_747_913_400, "adt_easy_20250522_3"),
+            ])
+            .await
+            .unwrap();
+
+        let ids = store
+            .get_contests(None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.contest_id.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            [
+                "adt_easy_20250604_1",
+                "adt_all_20250522_3",
+                "adt_easy_20250522_3"
+            ]
+        );
+        assert_eq!(store.get_contests(Some(1)).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn contests_by_problem_match_original_problems() {
+        let store = MemoryStore::new();
+        let mut with_problem = contest(1_747_913_400, "adt_all_20250522_3");
+        with_problem.problem_ids = vec![ProblemId::parse("abc369_e").unwrap()];
+        store
+            .batch_write_contests(vec![
+                with_problem,
+                contest(1_749_000_000, "adt_easy_20250604_1"),
+            ])
+            .await
+            .unwrap();
+
+        let contests = store
+            .get_contests_by_problem(&ProblemId::parse("abc369_e").unwrap())
+            .await
+            .unwrap();
+        assert_eq!(contests.len(), 1);
+        assert_eq!(contests[0].contest_id, "adt_all_20250522_3");
+    }
+
+    #[tokio::test]
+    async fn user_ac_problems_round_trip() {
+        let store = MemoryStore::new();
+        let user_id = UserId::parse("tourist").unwrap();
+        assert!(matches!(
+            store.get_user_ac_problems(&user_id).await,
+            Err(DdbError::NotFound)
+        ));
+
+        store
+            .add_ac_problems(
+                &user_id,
+                [(ProblemId::parse("abc407_a").unwrap(), None)].into(),
+            )
+            .await
+            .unwrap();
+
+        let stored = store.get_user_ac_problems(&user_id).await.unwrap();
+        assert_eq!(stored.problem_ids().collect::<Vec<_>>(), ["abc407_a"]);
+
+        let other = UserId::parse("other").unwrap();
+        let map = store
+            .batch_get_user_ac_problems(vec![user_id.clone(), other])
+            .await
+            .unwrap();
+        assert_eq!(map.keys().collect::<Vec<_>>(), [&user_id]);
+    }
+}
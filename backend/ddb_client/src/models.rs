@@ -1,7 +1,8 @@
 mod adt_contest;
 pub mod constants;
+pub(crate) mod item_size;
 pub mod traits;
 mod user_ac_problem;
 
 pub use adt_contest::AdtContestRecord;
-pub use user_ac_problem::UserAcProblemRecord;
+pub use user_ac_problem::{FirstAc, UserAcProblemRecord};
@@ -1,19 +1,23 @@
 use crate::models::traits::ToWriteRequest;
+use atcoder_ids::{AdtTier, ContestId, ProblemId, SubmissionId};
 use aws_sdk_dynamodb::types::AttributeValue;
 use chrono::{DateTime, Datelike, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Represents a single ADT contest record stored in DynamoDB.
 /// PK: "CONTEST#{YYYYMM}", SK: "{start_epoch_second}-{difficulty_order}"
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AdtContestRecord {
     #[serde(rename = "PK")]
     pub pk: String,
     #[serde(rename = "SK")]
     pub sk: String,
-    pub contest_id: String,
+    pub contest_id: ContestId,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub last_fetched_submission_id: Option<u64>,
+    pub last_fetched_submission_id: Option<SubmissionId>,
+    /// Original problem IDs included in this contest (e.g. "abc369_e").
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problem_ids: Vec<ProblemId>,
 }
 
 impl AdtContestRecord {
@@ -36,25 +40,28 @@ impl AdtContestRecord {
     }
 
     /// Generate the sort key (SK) AttributeValue based on contest start time.
-    pub fn sk_attr(start_epoch_second: u64, contest_id: &str) -> AttributeValue {
+    pub fn sk_attr(start_epoch_second: u64, contest_id: &ContestId) -> AttributeValue {
         let order = Self::difficulty_order(contest_id);
         let sk = format!("{:010}-{:02}", start_epoch_second, order);
         AttributeValue::S(sk)
     }
 
-    /// Generate all partition keys from current month down to ADT_START_YEAR_MONTH in descending order.
-    /// Returns a vector of AttributeValue representing PKs like ["CONTEST_202507", "CONTEST_202506", ..., "CONTEST_202310"]
+    /// Generate all partition keys from next month down to ADT_START_YEAR_MONTH in descending order.
+    /// Next month is included for contests pre-registered before they start.
+    /// Returns a vector of AttributeValue representing PKs like ["CONTEST_202508", "CONTEST_202507", ..., "CONTEST_202310"]
     pub fn generate_pks_descending() -> Vec<AttributeValue> {
         let now = Utc::now();
-        let current_year = now.year() as u32;
-        let current_month = now.month();
+        let (current_year, current_month) = (now.year() as u32, now.month());
 
         let start_year = Self::ADT_START_YEAR;
         let start_month = Self::ADT_START_MONTH;
 
         let mut pks = Vec::new();
-        let mut year = current_year;
-        let mut month = current_month;
+        let (mut year, mut month) = if current_month == 12 {
+            (current_year + 1, 1)
+        } else {
+            (current_year, current_month + 1)
+        };
 
         loop {
             let year_month = format!("{:04}{:02}", year, month);
@@ -88,15 +95,24 @@ impl AdtContestRecord {
             .unwrap_or(0)
     }
 
-    fn difficulty_order(contest_id: &str) -> u8 {
-        if contest_id.contains("_easy") {
-            1
-        } else if contest_id.contains("_medium") {
-            2
-        } else if contest_id.contains("_hard") {
-            3
-        } else {
-            4 // assume "_all" or default
+    /// Check whether this contest includes the given original problem.
+    pub fn contains_problem(&self, problem_id: &ProblemId) -> bool {
+        self.problem_ids.contains(problem_id)
+    }
+
+    /// ADT tier of this contest, if the contest ID is a well-formed ADT ID.
+    pub fn tier(&self) -> Option<AdtTier> {
+        self.contest_id.adt().map(|adt| adt.tier)
+    }
+
+    /// Orders contests starting at the same time by tier.
+    /// The values are part of stored SKs and must not change.
+    fn difficulty_order(contest_id: &ContestId) -> u8 {
+        match contest_id.adt().map(|adt| adt.tier) {
+            Some(AdtTier::Easy) => 1,
+            Some(AdtTier::Medium) => 2,
+            Some(AdtTier::Hard) => 3,
+            Some(AdtTier::All) | None => 4,
         }
     }
 }
@@ -1,2 +1,6 @@
 pub const PK_FIELD: &str = "PK";
 pub const SK_FIELD: &str = "SK";
+pub const VERSION_FIELD: &str = "version";
+/// Shards of a user's AC problems are kept below this estimated size,
+/// leaving headroom under DynamoDB's 400 KB item limit.
+pub const MAX_SHARD_ITEM_BYTES: usize = 300 * 1024;
@@ -1,40 +1,424 @@
-use crate::models::traits::ToWriteRequest;
+use crate::error::DdbError;
+use crate::models::item_size::{item_size, value_size};
+use atcoder_ids::{ContestId, ProblemId, SubmissionId, UserId};
 use aws_sdk_dynamodb::types::AttributeValue;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, btree_map::Entry};
 
 /// Represents a user's AC problem list stored in DynamoDB.
-/// PK format: "USER_AC#{user_id}", SK: "AC"
-#[derive(Debug, Serialize, Deserialize)]
+/// PK format: "USER_AC#{user_id}"
+/// The list is stored in shards with SK "AC#0", "AC#1", ... to stay under the item size limit.
+/// The first shard holds the version and the shard count. Older items use a single SK "AC".
+/// Records read through the store are reassembled, with SK "AC".
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct UserAcProblemRecord {
     #[serde(rename = "PK")]
     pub pk: String,
     #[serde(rename = "SK")]
     pub sk: String,
-    pub ac_problems: Vec<String>,
+    /// AC problems mapped to their first AC.
+    /// `None` for problems stored before first ACs were recorded.
+    #[serde(deserialize_with = "deserialize_ac_problems")]
+    pub ac_problems: BTreeMap<ProblemId, Option<FirstAc>>,
+    /// Incremented on every conditional update; 0 for items written without one.
+    #[serde(default)]
+    pub version: u64,
+    /// Number of shards; only set on the first shard item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_count: Option<usize>,
+}
+
+/// The earliest known accepted submission of a problem by a user.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FirstAc {
+    pub epoch_second: u64,
+    /// ADT contest the submission was made in.
+    pub contest_id: ContestId,
+    pub submission_id: SubmissionId,
+}
+
+impl FirstAc {
+    /// Whether `candidate` should replace `current` as the first AC.
+    /// A known AC replaces an unknown one, and the earlier of two known ACs wins.
+    fn is_earlier(candidate: &Option<Self>, current: &Option<Self>) -> bool {
+        match (candidate, current) {
+            (Some(candidate), Some(current)) => {
+                (candidate.epoch_second, candidate.submission_id)
+                    < (current.epoch_second, current.submission_id)
+            }
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+/// Accepts both the current map format and the legacy list of problem IDs.
+fn deserialize_ac_problems<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<ProblemId, Option<FirstAc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum AcProblems {
+        Map(BTreeMap<ProblemId, Option<FirstAc>>),
+        Legacy(Vec<ProblemId>),
+    }
+
+    Ok(match AcProblems::deserialize(deserializer)? {
+        AcProblems::Map(map) => map,
+        AcProblems::Legacy(problem_ids) => problem_ids.into_iter().map(|id| (id, None)).collect(),
+    })
 }
 
 impl UserAcProblemRecord {
+    /// Sort key (SK) of reassembled records and of items written before sharding.
+    pub const SK: &str = "AC";
+    /// Sort key (SK) prefix of shard items.
+    pub const SHARD_SK_PREFIX: &str = "AC#";
+
+    /// Create a record for the given user with version 0.
+    pub fn new(user_id: &UserId, ac_problems: BTreeMap<ProblemId, Option<FirstAc>>) -> Self {
+        Self {
+            pk: Self::pk(user_id),
+            sk: Self::SK.to_string(),
+            ac_problems,
+            version: 0,
+            shard_count: None,
+        }
+    }
+
+    /// Generate the partition key (PK) for a given user ID.
+    pub fn pk(user_id: &UserId) -> String {
+        format!("USER_AC#{}", user_id)
+    }
+
     /// Generate the partition key (PK) AttributeValue for a given user ID.
-    pub fn pk_attr(user_id: &str) -> AttributeValue {
-        AttributeValue::S(format!("USER_AC#{}", user_id))
+    pub fn pk_attr(user_id: &UserId) -> AttributeValue {
+        AttributeValue::S(Self::pk(user_id))
     }
 
-    /// Return the fixed sort key (SK) AttributeValue.
+    /// Return the sort key (SK) AttributeValue of items written before sharding.
     pub fn sk_attr() -> AttributeValue {
-        AttributeValue::S("AC".to_string())
+        AttributeValue::S(Self::SK.to_string())
     }
 
-    pub fn user_id(&self) -> String {
-        self.pk.strip_prefix("USER_AC#").unwrap_or("").to_string()
+    /// Generate the sort key (SK) of the shard with the given index.
+    pub fn shard_sk(index: usize) -> String {
+        format!("{}{}", Self::SHARD_SK_PREFIX, index)
     }
 
-    /// Merge accepted problems from another record into this one.
-    /// The resulting list will be sorted and deduplicated.
+    /// Generate the sort key (SK) AttributeValue of the shard with the given index.
+    pub fn shard_sk_attr(index: usize) -> AttributeValue {
+        AttributeValue::S(Self::shard_sk(index))
+    }
+
+    /// Index of this shard item, or `None` if this is not a shard.
+    pub fn shard_index(&self) -> Option<usize> {
+        self.sk.strip_prefix(Self::SHARD_SK_PREFIX)?.parse().ok()
+    }
+
+    /// Parse the user ID from the partition key, or `None` if the key is malformed.
+    pub fn user_id(&self) -> Option<UserId> {
+        self.pk.strip_prefix("USER_AC#")?.parse().ok()
+    }
+
+    /// AC problem IDs in sorted order.
+    pub fn problem_ids(&self) -> impl Iterator<Item = &ProblemId> {
+        self.ac_problems.keys()
+    }
+
+    /// Merge accepted problems from another record into this one,
+    /// keeping the earliest first AC of each problem.
     pub fn merge_ac_problems_from(&mut self, other: &Self) {
-        self.ac_problems.extend(other.ac_problems.iter().cloned());
-        self.ac_problems.sort();
-        self.ac_problems.dedup();
+        for (problem_id, first_ac) in &other.ac_problems {
+            match self.ac_problems.entry(problem_id.clone()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(first_ac.clone());
+                }
+                Entry::Occupied(mut entry) => {
+                    if FirstAc::is_earlier(first_ac, entry.get()) {
+                        entry.insert(first_ac.clone());
+                    }
+                }
+            }
+        }
     }
 }
 
-impl ToWriteRequest for UserAcProblemRecord {}
+impl UserAcProblemRecord {
+    /// Split this record into shard items whose estimated size stays within `max_item_bytes`.
+    /// Problems keep the shard they are stored in among the `current` items, so an update only
+    /// changes the shards that gained or updated problems. New problems go into the last shard,
+    /// and into new shards once it is full. The first shard carries the version and shard count.
+    pub fn into_shards(
+        self,
+        current: &[Self],
+        max_item_bytes: usize,
+    ) -> Result<Vec<Self>, DdbError> {
+        let Self {
+            pk,
+            mut ac_problems,
+            version,
+            ..
+        } = self;
+
+        // Size of a shard without problems, with room for any shard index and count
+        let empty_shard = Self {
+            pk: pk.clone(),
+            sk: Self::shard_sk(usize::MAX),
+            ac_problems: BTreeMap::new(),
+            version,
+            shard_count: Some(usize::MAX),
+        };
+        let base_size = item_size(&serde_dynamo::to_item(&empty_shard)?);
+        let entry_size = |problem_id: &ProblemId, first_ac: &Option<FirstAc>| {
+            Ok::<_, DdbError>(
+                1 + problem_id.as_str().len()
+                    + value_size(&serde_dynamo::to_attribute_value(first_ac)?),
+            )
+        };
+
+        // Keep the problems of the shards listed by the current first shard where they are
+        let current_count = current
+            .iter()
+            .find(|item| item.shard_index() == Some(0))
+            .and_then(|head| head.shard_count)
+            .unwrap_or(0);
+        let mut shards = Vec::with_capacity(current_count.max(1));
+        let mut sizes = Vec::with_capacity(current_count.max(1));
+        for index in 0..current_count {
+            let mut shard = BTreeMap::new();
+            let mut size = base_size;
+            if let Some(item) = current
+                .iter()
+                .find(|item| item.shard_index() == Some(index))
+            {
+                for problem_id in item.ac_problems.keys() {
+                    if let Some((problem_id, first_ac)) = ac_problems.remove_entry(problem_id) {
+                        size += entry_size(&problem_id, &first_ac)?;
+                        shard.insert(problem_id, first_ac);
+                    }
+                }
+            }
+            // Updated first ACs can grow a shard; move its last problems out if it got too large
+            while size > max_item_bytes && shard.len() > 1 {
+                let (problem_id, first_ac) = shard.pop_last().expect("shard should not be empty");
+                size -= entry_size(&problem_id, &first_ac)?;
+                ac_problems.insert(problem_id, first_ac);
+            }
+            shards.push(shard);
+            sizes.push(size);
+        }
+        if shards.is_empty() {
+            shards.push(BTreeMap::new());
+            sizes.push(base_size);
+        }
+
+        for (problem_id, first_ac) in ac_problems {
+            let size = entry_size(&problem_id, &first_ac)?;
+            let last = shards.len() - 1;
+            if sizes[last] + size > max_item_bytes && !shards[last].is_empty() {
+                shards.push(BTreeMap::new());
+                sizes.push(base_size);
+            }
+            let last = shards.len() - 1;
+            sizes[last] += size;
+            shards[last].insert(problem_id, first_ac);
+        }
+
+        let shard_count = shards.len();
+        Ok(shards
+            .into_iter()
+            .enumerate()
+            .map(|(index, ac_problems)| Self {
+                pk: pk.clone(),
+                sk: Self::shard_sk(index),
+                ac_problems,
+                version: if index == 0 { version } else { 0 },
+                shard_count: (index == 0).then_some(shard_count),
+            })
+            .collect())
+    }
+
+    /// Reassemble a record from the items stored for one user.
+    /// Uses the shards listed by the first shard if present, otherwise the pre-sharding item.
+    /// Shards beyond the shard count are left over from earlier writes and ignored.
+    pub fn from_shards(items: impl IntoIterator<Item = Self>) -> Option<Self> {
+        let mut head = None;
+        let mut legacy = None;
+        let mut shards = BTreeMap::new();
+        for item in items {
+            match item.shard_index() {
+                Some(0) => head = Some(item),
+                Some(index) => {
+                    shards.insert(index, item);
+                }
+                None if item.sk == Self::SK => legacy = Some(item),
+                None => {}
+            }
+        }
+
+        let Some(mut record) = head else {
+            return legacy;
+        };
+        let shard_count = record.shard_count.unwrap_or(1);
+        for shard in shards.range(1..shard_count).map(|(_, shard)| shard) {
+            record.merge_ac_problems_from(shard);
+        }
+        record.sk = Self::SK.to_string();
+        record.shard_count = None;
+        Some(record)
+    }
+}
+
+// No `ToWriteRequest` impl: AC records are only written through `add_ac_problems`,
+// whose conditional writes a blind BatchWriteItem put would overwrite.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::constants::MAX_SHARD_ITEM_BYTES;
+    use std::collections::HashMap;
+
+    fn first_ac(epoch_second: u64, submission_id: u64) -> Option<FirstAc> {
+        Some(FirstAc {
+            epoch_second,
+            contest_id: ContestId::parse("adt_all_20250522_3").unwrap(),
+            submission_id: SubmissionId::new(submission_id),
+        })
+    }
+
+    fn record(ac_problems: &[(&str, Option<FirstAc>)]) -> UserAcProblemRecord {
+        UserAcProblemRecord::new(
+            &UserId::parse("tourist").unwrap(),
+            ac_problems
+                .iter()
+                .map(|(id, first_ac)| (ProblemId::parse(id).unwrap(), first_ac.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn deserialize_legacy_problem_list() {
+        let item = HashMap::from([
+            (
+                "PK".to_string(),
+                AttributeValue::S("USER_AC#tourist".into()),
+            ),
+            ("SK".to_string(), AttributeValue::S("AC".into())),
+            (
+                "ac_problems".to_string(),
+                AttributeValue::L(vec![
+                    AttributeValue::S("abc407_a".into()),
+                    AttributeValue::S("abc301_b".into()),
+                ]),
+            ),
+        ]);
+
+        let record: UserAcProblemRecord = serde_dynamo::from_item(item).unwrap();
+        assert_eq!(record.version, 0);
+        assert_eq!(
+            record.problem_ids().collect::<Vec<_>>(),
+            ["abc301_b", "abc407_a"]
+        );
+        assert!(record.ac_problems.values().all(Option::is_none));
+    }
+
+    #[test]
+    fn first_acs_round_trip_through_dynamodb_item() {
+        let record = record(&[("abc407_a", first_ac(100, 1)), ("abc301_b", None)]);
+        let item: HashMap<String, AttributeValue> = serde_dynamo::to_item(&record).unwrap();
+        let restored: UserAcProblemRecord = serde_dynamo::from_item(item).unwrap();
+        assert_eq!(restored.ac_problems, record.ac_problems);
+    }
+
+    #[test]
+    fn shards_split_past_threshold_and_reassemble() {
+        let problems = (0..40)
+            .map(|i| (format!("abc{:03}_a", 100 + i), first_ac(100 + i, i)))
+            .collect::<Vec<_>>();
+        let mut original = record(
+            &problems
+                .iter()
+                .map(|(id, ac)| (id.as_str(), ac.clone()))
+                .collect::<Vec<_>>(),
+        );
+        original.version = 7;
+
+        let shards = original.clone().into_shards(&[], 1_000).unwrap();
+        assert!(shards.len() > 1);
+        for (index, shard) in shards.iter().enumerate() {
+            assert_eq!(shard.sk, format!("AC#{index}"));
+            let item: HashMap<String, AttributeValue> = serde_dynamo::to_item(shard).unwrap();
+            assert!(item_size(&item) <= 1_000, "shard {index} is too large");
+        }
+        assert_eq!(shards[0].shard_count, Some(shards.len()));
+        assert_eq!(shards[0].version, 7);
+
+        let restored = UserAcProblemRecord::from_shards(shards).unwrap();
+        assert_eq!(restored.sk, "AC");
+        assert_eq!(restored.ac_problems, original.ac_problems);
+        assert_eq!(restored.version, 7);
+        assert_eq!(restored.shard_count, None);
+
+        // Small records fit in a single shard
+        assert_eq!(
+            original
+                .into_shards(&[], MAX_SHARD_ITEM_BYTES)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn from_shards_ignores_stale_shards_and_falls_back_to_legacy_item() {
+        let legacy = record(&[("abc300_a", None)]);
+        let mut shards = record(&[("abc407_a", None), ("abc407_b", None)])
+            .into_shards(&[], MAX_SHARD_ITEM_BYTES)
+            .unwrap();
+        let mut stale = record(&[("abc999_z", None)]);
+        stale.sk = UserAcProblemRecord::shard_sk(1);
+        shards.push(stale);
+        shards.push(legacy.clone());
+
+        let restored = UserAcProblemRecord::from_shards(shards).unwrap();
+        assert_eq!(
+            restored.problem_ids().collect::<Vec<_>>(),
+            ["abc407_a", "abc407_b"]
+        );
+
+        let restored = UserAcProblemRecord::from_shards([legacy]).unwrap();
+        assert_eq!(restored.problem_ids().collect::<Vec<_>>(), ["abc300_a"]);
+        assert!(UserAcProblemRecord::from_shards([]).is_none());
+    }
+
+    #[test]
+    fn merge_keeps_earliest_known_first_ac() {
+        let mut new = record(&[
+            ("abc407_a", first_ac(200, 20)),
+            ("abc407_b", first_ac(200, 21)),
+            ("abc407_c", first_ac(200, 22)),
+        ]);
+        let existing = record(&[
+            ("abc407_a", first_ac(100, 10)),
+            ("abc407_b", None),
+            ("abc301_d", None),
+        ]);
+
+        new.merge_ac_problems_from(&existing);
+
+        assert_eq!(
+            new.ac_problems,
+            record(&[
+                ("abc301_d", None),
+                ("abc407_a", first_ac(100, 10)),
+                ("abc407_b", first_ac(200, 21)),
+                ("abc407_c", first_ac(200, 22)),
+            ])
+            .ac_problems
+        );
+    }
+}
@@ -1,8 +1,10 @@
+mod add_ac_problems;
 mod batch_get_user_ac_problems;
 mod batch_write_items;
 mod get_contests;
 mod get_user_ac_problems;
 
+pub use add_ac_problems::*;
 pub use batch_get_user_ac_problems::*;
 pub use batch_write_items::*;
 pub use get_contests::*;
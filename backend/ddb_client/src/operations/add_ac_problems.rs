@@ -0,0 +1,336 @@
+use crate::constants::{
+    BASE_BACKOFF_MILLIS, MAX_BACKOFF_MILLIS, MAX_CONFLICT_RETRIES, MAX_TRANSACT_BYTES,
+    MAX_TRANSACT_ITEMS,
+};
+use crate::error::DdbError;
+use crate::models::{
+    FirstAc, UserAcProblemRecord,
+    constants::{MAX_SHARD_ITEM_BYTES, PK_FIELD, SK_FIELD, VERSION_FIELD},
+    item_size::item_size,
+};
+use crate::operations::get_user_ac_problems::query_user_ac_items;
+use atcoder_ids::{ProblemId, UserId};
+use aws_sdk_dynamodb::{
+    Client,
+    error::SdkError,
+    operation::transact_write_items::TransactWriteItemsError,
+    types::{AttributeValue, Delete, Put, TransactWriteItem},
+};
+use std::collections::{BTreeMap, HashMap};
+use tokio::time::{Duration, sleep};
+
+/// Add AC problems to a user's record without losing concurrent updates.
+/// Reads all of the record's shards, merges the new problems into it (keeping the earliest
+/// first AC) and re-shards it. The changed shards are written in one transaction that only
+/// succeeds if the first shard's `version` is still the one read; on conflict it retries from
+/// the read with exponential backoff. The transaction also deletes the pre-sharding item and
+/// shards that are no longer used.
+pub async fn add_ac_problems(
+    client: &Client,
+    table_name: &str,
+    user_id: &UserId,
+    problems: BTreeMap<ProblemId, Option<FirstAc>>,
+) -> Result<(), DdbError> {
+    let mut backoff = BASE_BACKOFF_MILLIS;
+
+    for _ in 0..=MAX_CONFLICT_RETRIES {
+        let items = query_user_ac_items(client, table_name, user_id, true).await?;
+        let current = UserAcProblemRecord::from_shards(items.clone());
+
+        let mut record = UserAcProblemRecord::new(user_id, problems.clone());
+        if let Some(current) = &current {
+            record.merge_ac_problems_from(current);
+            // Nothing new to write
+            if record.ac_problems == current.ac_problems {
+                return Ok(());
+            }
+        }
+        record.version = current.as_ref().map_or(0, |record| record.version) + 1;
+
+        let actions = transact_items(table_name, user_id, &items, record, MAX_SHARD_ITEM_BYTES)?;
+        match client
+            .transact_write_items()
+            .set_transact_items(Some(actions))
+            .send()
+            .await
+        {
+            Ok(_) => return Ok(()),
+            Err(err) if is_write_conflict(&err) => {
+                sleep(Duration::from_millis(backoff)).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF_MILLIS);
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Err(DdbError::ConditionalWriteConflict)
+}
+
+/// Build the transaction that replaces the user's current `items` with `record`.
+/// Writes the first shard and the shards whose problems changed, and deletes the pre-sharding
+/// item and unused shards. Fails without sending anything if the transaction would exceed
+/// DynamoDB's limits, since retrying could never succeed.
+fn transact_items(
+    table_name: &str,
+    user_id: &UserId,
+    items: &[UserAcProblemRecord],
+    record: UserAcProblemRecord,
+    max_item_bytes: usize,
+) -> Result<Vec<TransactWriteItem>, DdbError> {
+    let head = items.iter().find(|item| item.shard_index() == Some(0));
+    let legacy = items.iter().find(|item| item.sk == UserAcProblemRecord::SK);
+    let shards = record.into_shards(items, max_item_bytes)?;
+
+    let mut actions = Vec::new();
+    let mut bytes = 0;
+    for shard in &shards {
+        let is_head = shard.shard_index() == Some(0);
+        // The first shard carries the new version, other unchanged shards can stay as they are
+        let unchanged = items
+            .iter()
+            .any(|item| item.sk == shard.sk && item.ac_problems == shard.ac_problems);
+        if !is_head && unchanged {
+            continue;
+        }
+
+        let item = serde_dynamo::to_item(shard)?;
+        bytes += item_size(&item);
+        let mut put = Put::builder().table_name(table_name).set_item(Some(item));
+        // The first shard guards the whole record
+        if is_head {
+            put = match head {
+                Some(head) => put
+                    .condition_expression(version_condition(head.version))
+                    .expression_attribute_names("#version", VERSION_FIELD)
+                    .expression_attribute_values(
+                        ":version",
+                        AttributeValue::N(head.version.to_string()),
+                    ),
+                None => put
+                    .condition_expression("attribute_not_exists(#pk)")
+                    .expression_attribute_names("#pk", PK_FIELD),
+            };
+        }
+        actions.push(TransactWriteItem::builder().put(put.build()?).build());
+    }
+    if let Some(legacy) = legacy {
+        let key = key(user_id, UserAcProblemRecord::sk_attr());
+        bytes += item_size(&key);
+        let delete = Delete::builder()
+            .table_name(table_name)
+            .set_key(Some(key))
+            .condition_expression(version_condition(legacy.version))
+            .expression_attribute_names("#version", VERSION_FIELD)
+            .expression_attribute_values(":version", AttributeValue::N(legacy.version.to_string()));
+        actions.push(TransactWriteItem::builder().delete(delete.build()?).build());
+    }
+    for stale in items
+        .iter()
+        .filter_map(UserAcProblemRecord::shard_index)
+        .filter(|index| *index >= shards.len())
+    {
+        let key = key(user_id, UserAcProblemRecord::shard_sk_attr(stale));
+        bytes += item_size(&key);
+        let delete = Delete::builder().table_name(table_name).set_key(Some(key));
+        actions.push(TransactWriteItem::builder().delete(delete.build()?).build());
+    }
+
+    if actions.len() > MAX_TRANSACT_ITEMS || bytes > MAX_TRANSACT_BYTES {
+        return Err(DdbError::TransactionTooLarge {
+            actions: actions.len(),
+            bytes,
+        });
+    }
+    Ok(actions)
+}
+
+/// Primary key of one of the user's AC items.
+fn key(user_id: &UserId, sk: AttributeValue) -> HashMap<String, AttributeValue> {
+    HashMap::from([
+        (PK_FIELD.to_string(), UserAcProblemRecord::pk_attr(user_id)),
+        (SK_FIELD.to_string(), sk),
+    ])
+}
+
+/// Condition requiring an item to still have the version read.
+/// Items written without a conditional update may have no version attribute.
+fn version_condition(version: u64) -> &'static str {
+    if version == 0 {
+        "attribute_not_exists(#version) OR #version = :version"
+    } else {
+        "#version = :version"
+    }
+}
+
+/// Whether the transaction was cancelled only because of concurrent updates.
+fn is_write_conflict(err: &SdkError<TransactWriteItemsError>) -> bool {
+    let Some(TransactWriteItemsError::TransactionCanceledException(canceled)) =
+        err.as_service_error()
+    else {
+        return false;
+    };
+    canceled.cancellation_reasons().iter().all(|reason| {
+        matches!(
+            reason.code(),
+            None | Some("None" | "ConditionalCheckFailed" | "TransactionConflict")
+        )
+    })
+}
+
+/// The tests that write to DynamoDB need DynamoDB Local and are ignored by default. To run them:
+///
+/// ```bash
+/// docker run -d -p 8000:8000 amazon/dynamodb-local
+/// DYNAMODB_LOCAL_ENDPOINT=http://localhost:8000 cargo test -p ddb_client -- --ignored
+/// ```
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::get_user_ac_problems;
+    use aws_sdk_dynamodb::{
+        config::{BehaviorVersion, Credentials, Region},
+        types::{AttributeDefinition, BillingMode, KeySchemaElement, KeyType, ScalarAttributeType},
+    };
+    use futures::future::try_join_all;
+
+    /// Endpoint of a running DynamoDB Local, e.g. "http://localhost:8000".
+    const DYNAMODB_LOCAL_ENDPOINT_ENV: &str = "DYNAMODB_LOCAL_ENDPOINT";
+
+    async fn local_client_with_table(table_name: &str) -> Client {
+        let endpoint = std::env::var(DYNAMODB_LOCAL_ENDPOINT_ENV)
+            .unwrap_or_else(|_| panic!("{} is not set", DYNAMODB_LOCAL_ENDPOINT_ENV));
+        let config = aws_sdk_dynamodb::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .endpoint_url(endpoint)
+            .region(Region::new("ap-northeast-1"))
+            .credentials_provider(Credentials::for_tests())
+            .build();
+        let client = Client::from_conf(config);
+
+        let key = |name: &str, key_type| {
+            KeySchemaElement::builder()
+                .attribute_name(name)
+                .key_type(key_type)
+                .build()
+                .unwrap()
+        };
+        let attribute = |name: &str| {
+            AttributeDefinition::builder()
+                .attribute_name(name)
+                .attribute_type(ScalarAttributeType::S)
+                .build()
+                .unwrap()
+        };
+        client
+            .create_table()
+            .table_name(table_name)
+            .key_schema(key(PK_FIELD, KeyType::Hash))
+            .key_schema(key(SK_FIELD, KeyType::Range))
+            .attribute_definitions(attribute(PK_FIELD))
+            .attribute_definitions(attribute(SK_FIELD))
+            .billing_mode(BillingMode::PayPerRequest)
+            .send()
+            .await
+            .unwrap();
+
+        client
+    }
+
+    fn problems(prefix: &str, count: usize) -> BTreeMap<ProblemId, Option<FirstAc>> {
+        (0..count)
+            .map(|i| {
+                (
+                    ProblemId::parse(&format!("{prefix}{i:06}_a")).unwrap(),
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    fn put_sks(actions: &[TransactWriteItem]) -> Vec<String> {
+        actions
+            .iter()
+            .filter_map(|action| action.put())
+            .map(|put| put.item()[SK_FIELD].as_s().unwrap().clone())
+            .collect()
+    }
+
+    #[test]
+    fn transact_items_writes_only_changed_shards() {
+        let user_id = UserId::parse("tourist").unwrap();
+        let mut current = UserAcProblemRecord::new(&user_id, problems("abc", 1_000));
+        current.version = 3;
+        let items = current.clone().into_shards(&[], 1_000).unwrap();
+        assert!(items.len() > 10);
+
+        // A new problem that sorts before every stored one still goes into the last shard
+        let mut record = UserAcProblemRecord::new(&user_id, problems("aaa", 1));
+        record.merge_ac_problems_from(&current);
+        record.version = 4;
+        let actions = transact_items("table", &user_id, &items, record, 1_000).unwrap();
+
+        let last = items.last().unwrap().sk.clone();
+        assert_eq!(put_sks(&actions), ["AC#0".to_string(), last]);
+        assert_eq!(actions.len(), 2);
+    }
+
+    #[test]
+    fn transact_items_rejects_record_over_transaction_limits() {
+        let user_id = UserId::parse("tourist").unwrap();
+        let mut record = UserAcProblemRecord::new(&user_id, problems("abc", 400_000));
+        record.version = 1;
+
+        let result = transact_items("table", &user_id, &[], record, MAX_SHARD_ITEM_BYTES);
+        assert!(matches!(
+            result,
+            Err(DdbError::TransactionTooLarge { bytes, .. }) if bytes > MAX_TRANSACT_BYTES
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DynamoDB Local; set DYNAMODB_LOCAL_ENDPOINT and run with --ignored"]
+    async fn concurrent_adds_keep_every_problem() {
+        let table_name = format!("add_ac_problems_{}", std::process::id());
+        let client = local_client_with_table(&table_name).await;
+        let user_id = UserId::parse("tourist").unwrap();
+
+        // An item written by the old code path has no version attribute
+        let legacy = UserAcProblemRecord::new(
+            &user_id,
+            [(ProblemId::parse("abc300_a").unwrap(), None)].into(),
+        );
+        client
+            .put_item()
+            .table_name(&table_name)
+            .set_item(Some(serde_dynamo::to_item(&legacy).unwrap()))
+            .send()
+            .await
+            .unwrap();
+
+        let problem_ids = ["a", "b", "c", "d", "e", "f", "g", "h"]
+            .map(|index| ProblemId::parse(&format!("abc407_{index}")).unwrap());
+        try_join_all(problem_ids.iter().map(|problem_id| {
+            add_ac_problems(
+                &client,
+                &table_name,
+                &user_id,
+                [(problem_id.clone(), None)].into(),
+            )
+        }))
+        .await
+        .unwrap();
+
+        let record = get_user_ac_problems(&client, &table_name, &user_id)
+            .await
+            .unwrap();
+        client
+            .delete_table()
+            .table_name(&table_name)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(record.ac_problems.len(), problem_ids.len() + 1);
+        assert_eq!(record.version, problem_ids.len() as u64);
+    }
+}
@@ -4,38 +4,86 @@ use crate::models::{
     UserAcProblemRecord,
     constants::{PK_FIELD, SK_FIELD},
 };
-use aws_sdk_dynamodb::{Client, types::KeysAndAttributes};
+use atcoder_ids::UserId;
+use aws_sdk_dynamodb::{
+    Client,
+    types::{AttributeValue, KeysAndAttributes},
+};
 use std::collections::HashMap;
 use tokio::time::{Duration, sleep};
 
 /// Retrieve multiple users' AC problems using BatchGetItem.
-/// Batches up to 100 items per request, with retries using exponential backoff.
+/// Reads each user's first shard (and pre-sharding item), then the remaining shards of users
+/// whose record spans several, and reassembles them.
 pub async fn batch_get_user_ac_problems(
     client: &Client,
     table_name: &str,
-    user_ids: Vec<String>,
-) -> Result<HashMap<String, UserAcProblemRecord>, DdbError> {
-    let mut result_map = HashMap::new();
-    let mut start = 0;
+    user_ids: Vec<UserId>,
+) -> Result<HashMap<UserId, UserAcProblemRecord>, DdbError> {
+    let key = |user_id: &UserId, sk: AttributeValue| {
+        HashMap::from([
+            (PK_FIELD.to_string(), UserAcProblemRecord::pk_attr(user_id)),
+            (SK_FIELD.to_string(), sk),
+        ])
+    };
 
-    while start < user_ids.len() {
-        let end = (start + MAX_BATCH_GET).min(user_ids.len());
-        let batch = &user_ids[start..end];
+    let head_keys = user_ids
+        .iter()
+        .flat_map(|user_id| {
+            [
+                key(user_id, UserAcProblemRecord::shard_sk_attr(0)),
+                key(user_id, UserAcProblemRecord::sk_attr()),
+            ]
+        })
+        .collect();
+    let mut items_by_user: HashMap<UserId, Vec<UserAcProblemRecord>> = HashMap::new();
+    for record in batch_get_items(client, table_name, head_keys).await? {
+        if let Some(user_id) = record.user_id() {
+            items_by_user.entry(user_id).or_default().push(record);
+        }
+    }
 
-        let keys = batch
-            .iter()
-            .map(|user_id| {
-                HashMap::from([
-                    (PK_FIELD.to_string(), UserAcProblemRecord::pk_attr(user_id)),
-                    (SK_FIELD.to_string(), UserAcProblemRecord::sk_attr()),
-                ])
-            })
-            .collect::<Vec<_>>();
+    let shard_keys = items_by_user
+        .iter()
+        .flat_map(|(user_id, records)| {
+            let shard_count = records
+                .iter()
+                .find(|record| record.shard_index() == Some(0))
+                .and_then(|head| head.shard_count)
+                .unwrap_or(1);
+            (1..shard_count).map(|index| key(user_id, UserAcProblemRecord::shard_sk_attr(index)))
+        })
+        .collect();
+    for record in batch_get_items(client, table_name, shard_keys).await? {
+        if let Some(user_id) = record.user_id() {
+            items_by_user.entry(user_id).or_default().push(record);
+        }
+    }
 
+    Ok(items_by_user
+        .into_iter()
+        .filter_map(|(user_id, records)| {
+            UserAcProblemRecord::from_shards(records).map(|record| (user_id, record))
+        })
+        .collect())
+}
+
+/// Get AC items by key using BatchGetItem.
+/// Batches up to 100 items per request, with retries using exponential backoff.
+async fn batch_get_items(
+    client: &Client,
+    table_name: &str,
+    keys: Vec<HashMap<String, AttributeValue>>,
+) -> Result<Vec<UserAcProblemRecord>, DdbError> {
+    let mut records = Vec::new();
+
+    for batch in keys.chunks(MAX_BATCH_GET) {
         let mut request_items = HashMap::new();
         request_items.insert(
             table_name.to_string(),
-            KeysAndAttributes::builder().set_keys(Some(keys)).build()?,
+            KeysAndAttributes::builder()
+                .set_keys(Some(batch.to_vec()))
+                .build()?,
         );
 
         let mut retries = 0;
@@ -49,13 +97,11 @@ pub async fn batch_get_user_ac_problems(
                 .await?;
 
             // Extract items from the response
-            if let Some(responses) = response.responses() {
-                if let Some(items) = responses.get(table_name) {
-                    for item in items.clone() {
-                        let record: UserAcProblemRecord = serde_dynamo::from_item(item)?;
-                        let user_id = record.user_id();
-                        result_map.insert(user_id, record);
-                    }
+            if let Some(responses) = response.responses()
+                && let Some(items) = responses.get(table_name)
+            {
+                for item in items.clone() {
+                    records.push(serde_dynamo::from_item(item)?);
                 }
             }
 
@@ -76,9 +122,7 @@ pub async fn batch_get_user_ac_problems(
             sleep(Duration::from_millis(backoff)).await;
             backoff *= 2;
         }
-
-        start = end;
     }
 
-    Ok(result_map)
+    Ok(records)
 }
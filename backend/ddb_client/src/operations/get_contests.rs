@@ -1,5 +1,6 @@
 use crate::error::DdbError;
 use crate::models::{AdtContestRecord, constants::PK_FIELD};
+use atcoder_ids::ProblemId;
 use aws_sdk_dynamodb::{Client, types::AttributeValue};
 use std::collections::HashMap;
 
@@ -22,10 +23,10 @@ pub async fn get_contests(
         for contest in partition_contests {
             all_contests.push(contest);
 
-            if let Some(max) = max_items {
-                if all_contests.len() >= max {
-                    return Ok(all_contests);
-                }
+            if let Some(max) = max_items
+                && all_contests.len() >= max
+            {
+                return Ok(all_contests);
             }
         }
     }
@@ -33,6 +34,21 @@ pub async fn get_contests(
     Ok(all_contests)
 }
 
+/// Retrieve all ADT contests that include the given original problem ID.
+/// Contest records are small, so this scans every partition and filters in memory.
+pub async fn get_contests_by_problem(
+    client: &Client,
+    table_name: &str,
+    problem_id: &ProblemId,
+) -> Result<Vec<AdtContestRecord>, DdbError> {
+    let contests = get_contests(client, table_name, None).await?;
+
+    Ok(contests
+        .into_iter()
+        .filter(|contest| contest.contains_problem(problem_id))
+        .collect())
+}
+
 /// Query a single partition (year-month) for ADT contests.
 async fn query_single_partition(
     client: &Client,
@@ -63,10 +79,10 @@ async fn query_single_partition(
                 let record = serde_dynamo::from_item(item)?;
                 contests.push(record);
 
-                if let Some(max) = max_items {
-                    if contests.len() >= max {
-                        return Ok(contests);
-                    }
+                if let Some(max) = max_items
+                    && contests.len() >= max
+                {
+                    return Ok(contests);
                 }
             }
         }
@@ -3,25 +3,61 @@ use crate::models::{
     UserAcProblemRecord,
     constants::{PK_FIELD, SK_FIELD},
 };
-use aws_sdk_dynamodb::Client;
+use atcoder_ids::UserId;
+use aws_sdk_dynamodb::{Client, types::AttributeValue};
+use std::collections::HashMap;
 
 /// Retrieve the AC problems for a user from DynamoDB.
+/// Queries all of the user's shard items and reassembles them into one record.
 pub async fn get_user_ac_problems(
     client: &Client,
     table_name: &str,
-    user_id: &str,
+    user_id: &UserId,
 ) -> Result<UserAcProblemRecord, DdbError> {
-    let result = client
-        .get_item()
-        .table_name(table_name)
-        .key(PK_FIELD, UserAcProblemRecord::pk_attr(user_id))
-        .key(SK_FIELD, UserAcProblemRecord::sk_attr())
-        .send()
-        .await?;
+    let items = query_user_ac_items(client, table_name, user_id, false).await?;
 
-    let item = result.item.ok_or(DdbError::NotFound)?;
+    UserAcProblemRecord::from_shards(items).ok_or(DdbError::NotFound)
+}
+
+/// Query every AC item of a user: the shards and the item written before sharding.
+pub(crate) async fn query_user_ac_items(
+    client: &Client,
+    table_name: &str,
+    user_id: &UserId,
+    consistent_read: bool,
+) -> Result<Vec<UserAcProblemRecord>, DdbError> {
+    let mut records = Vec::new();
+    let mut last_evaluated_key: Option<HashMap<String, AttributeValue>> = None;
+
+    loop {
+        let mut req = client
+            .query()
+            .table_name(table_name)
+            .key_condition_expression("#pk = :pk AND begins_with(#sk, :sk)")
+            .expression_attribute_names("#pk", PK_FIELD)
+            .expression_attribute_names("#sk", SK_FIELD)
+            .expression_attribute_values(":pk", UserAcProblemRecord::pk_attr(user_id))
+            .expression_attribute_values(":sk", UserAcProblemRecord::sk_attr())
+            .consistent_read(consistent_read);
+
+        if let Some(ref lek) = last_evaluated_key {
+            req = req.set_exclusive_start_key(Some(lek.clone()));
+        }
+
+        let result = req.send().await?;
+
+        if let Some(items) = result.items {
+            for item in items {
+                records.push(serde_dynamo::from_item(item)?);
+            }
+        }
 
-    let record = serde_dynamo::from_item(item)?;
+        if let Some(lek) = result.last_evaluated_key {
+            last_evaluated_key = Some(lek);
+        } else {
+            break;
+        }
+    }
 
-    Ok(record)
+    Ok(records)
 }
@@ -1,8 +1,10 @@
 use crate::error::DdbError;
-use crate::models::{AdtContestRecord, UserAcProblemRecord, traits::ToWriteRequest};
+use crate::models::{AdtContestRecord, FirstAc, UserAcProblemRecord, traits::ToWriteRequest};
 use crate::operations;
+use crate::store::{AcStore, ContestStore};
+use atcoder_ids::{ProblemId, UserId};
 use aws_sdk_dynamodb::Client;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Service for interacting with DynamoDB for AtCoder Problems ADT Sync.
 #[derive(Clone)]
@@ -22,35 +24,61 @@ impl DdbService {
         }
     }
 
+    /// Write multiple items to DynamoDB using BatchWriteItem.
+    pub async fn batch_write_items<T: ToWriteRequest>(
+        &self,
+        items: Vec<T>,
+    ) -> Result<(), DdbError> {
+        operations::batch_write_items(&self.client, &self.table_name, items).await
+    }
+}
+
+impl AcStore for DdbService {
     /// Retrieve a user's AC problems from DynamoDB.
-    pub async fn get_user_ac_problems(
+    async fn get_user_ac_problems(
         &self,
-        user_id: &str,
+        user_id: &UserId,
     ) -> Result<UserAcProblemRecord, DdbError> {
         operations::get_user_ac_problems(&self.client, &self.table_name, user_id).await
     }
 
     /// Retrieve multiple users' AC problems using BatchGetItem.
-    pub async fn batch_get_user_ac_problems(
+    async fn batch_get_user_ac_problems(
         &self,
-        user_ids: Vec<String>,
-    ) -> Result<HashMap<String, UserAcProblemRecord>, DdbError> {
+        user_ids: Vec<UserId>,
+    ) -> Result<HashMap<UserId, UserAcProblemRecord>, DdbError> {
         operations::batch_get_user_ac_problems(&self.client, &self.table_name, user_ids).await
     }
 
-    /// Retrieve ADT contests (optionally limited by max count).
-    pub async fn get_contests(
+    /// Merge AC problems into the user's record using transactional writes conditioned on its version.
+    async fn add_ac_problems(
+        &self,
+        user_id: &UserId,
+        problems: BTreeMap<ProblemId, Option<FirstAc>>,
+    ) -> Result<(), DdbError> {
+        operations::add_ac_problems(&self.client, &self.table_name, user_id, problems).await
+    }
+}
+
+impl ContestStore for DdbService {
+    /// Retrieve ADT contests from DynamoDB, querying each monthly partition.
+    async fn get_contests(
         &self,
         max_items: Option<usize>,
     ) -> Result<Vec<AdtContestRecord>, DdbError> {
         operations::get_contests(&self.client, &self.table_name, max_items).await
     }
 
-    /// Write multiple items to DynamoDB using BatchWriteItem.
-    pub async fn batch_write_items<T: ToWriteRequest>(
+    /// Retrieve ADT contests that include the given original problem ID.
+    async fn get_contests_by_problem(
         &self,
-        items: Vec<T>,
-    ) -> Result<(), DdbError> {
-        operations::batch_write_items(&self.client, &self.table_name, items).await
+        problem_id: &ProblemId,
+    ) -> Result<Vec<AdtContestRecord>, DdbError> {
+        operations::get_contests_by_problem(&self.client, &self.table_name, problem_id).await
+    }
+
+    /// Write contest records to DynamoDB using BatchWriteItem.
+    async fn batch_write_contests(&self, records: Vec<AdtContestRecord>) -> Result<(), DdbError> {
+        self.batch_write_items(records).await
     }
 }
@@ -0,0 +1,481 @@
+use crate::error::DdbError;
+use crate::models::{AdtContestRecord, FirstAc, UserAcProblemRecord};
+use crate::store::{AcStore, ContestStore};
+use atcoder_ids::{ContestId, ProblemId, SubmissionId, UserId};
+use rusqlite::{Connection, Transaction, TransactionBehavior, params};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Schema migrations, applied in order. The number of applied migrations is kept in
+/// `PRAGMA user_version`, so new migrations must only ever be appended.
+const MIGRATIONS: &[&str] = &[
+    include_str!("../migrations/0001_init.sql"),
+    include_str!("../migrations/0002_first_ac.sql"),
+    include_str!("../migrations/0003_contest_problem_index.sql"),
+];
+
+/// SQLite-backed store for running the sync on a single machine without AWS.
+/// Records keep the same keys and semantics as in DynamoDB; writes replace whole records.
+/// Queries run synchronously on the calling task, which is fine for the small local database.
+#[derive(Clone, Debug)]
+pub struct SqliteStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteStore {
+    /// Open (or create) the database at `path` and apply pending migrations.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DdbError> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Open a private in-memory database, mainly for tests.
+    pub fn open_in_memory() -> Result<Self, DdbError> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(mut conn: Connection) -> Result<Self, DdbError> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        migrate(&mut conn)?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().expect("SQLite connection lock poisoned")
+    }
+}
+
+/// Apply the migrations that have not been applied yet, each in its own transaction.
+fn migrate(conn: &mut Connection) -> Result<(), DdbError> {
+    let applied: u32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+    for (version, sql) in MIGRATIONS.iter().enumerate().skip(applied as usize) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(sql)?;
+        tx.pragma_update(None, "user_version", version as u32 + 1)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+/// Parse an ID read back from the database.
+fn parse_stored<T: std::str::FromStr<Err = atcoder_ids::IdParseError>>(
+    value: String,
+) -> Result<T, DdbError> {
+    value
+        .parse()
+        .map_err(|err: atcoder_ids::IdParseError| DdbError::SerdeConversionError(err.to_string()))
+}
+
+/// Read a user's AC problems with their first ACs, or `None` if the user has none.
+fn select_user_ac_problems(
+    conn: &Connection,
+    user_id: &UserId,
+) -> Result<Option<UserAcProblemRecord>, DdbError> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT problem_id, first_ac_epoch_second, first_ac_contest_id, first_ac_submission_id
+         FROM user_ac_problems WHERE user_id = ?1",
+    )?;
+    let rows = stmt
+        .query_map([user_id.as_str()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<i64>>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if rows.is_empty() {
+        return Ok(None);
+    }
+
+    let mut ac_problems = BTreeMap::new();
+    for (problem_id, epoch_second, contest_id, submission_id) in rows {
+        let first_ac = match (epoch_second, contest_id, submission_id) {
+            (Some(epoch_second), Some(contest_id), Some(submission_id)) => Some(FirstAc {
+                epoch_second: epoch_second as u64,
+                contest_id: parse_stored(contest_id)?,
+                submission_id: SubmissionId::new(submission_id as u64),
+            }),
+            _ => None,
+        };
+        ac_problems.insert(parse_stored(problem_id)?, first_ac);
+    }
+
+    Ok(Some(UserAcProblemRecord::new(user_id, ac_problems)))
+}
+
+/// Replace a user's AC problems with the given record.
+fn replace_user_ac_problems(
+    tx: &Transaction<'_>,
+    record: &UserAcProblemRecord,
+) -> Result<(), DdbError> {
+    let user_id = record.user_id().ok_or_else(|| {
+        DdbError::SerdeConversionError(format!("Malformed user AC problem PK: {}", record.pk))
+    })?;
+
+    tx.execute(
+        "DELETE FROM user_ac_problems WHERE user_id = ?1",
+        [user_id.as_str()],
+    )?;
+    let mut insert = tx.prepare_cached(
+        "INSERT INTO user_ac_problems (
+             user_id, problem_id, first_ac_epoch_second, first_ac_contest_id, first_ac_submission_id
+         ) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    for (problem_id, first_ac) in &record.ac_problems {
+        insert.execute(params![
+            user_id.as_str(),
+            problem_id.as_str(),
+            first_ac.as_ref().map(|ac| ac.epoch_second as i64),
+            first_ac.as_ref().map(|ac| ac.contest_id.as_str()),
+            first_ac.as_ref().map(|ac| ac.submission_id.get() as i64),
+        ])?;
+    }
+
+    Ok(())
+}
+
+/// Read a contest's original problems in task order.
+fn select_contest_problems(
+    conn: &Connection,
+    pk: &str,
+    sk: &str,
+) -> Result<Vec<ProblemId>, DdbError> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT problem_id FROM adt_contest_problems WHERE pk = ?1 AND sk = ?2 ORDER BY position",
+    )?;
+    stmt.query_map([pk, sk], |row| row.get::<_, String>(0))?
+        .map(|problem_id| parse_stored(problem_id?))
+        .collect()
+}
+
+/// Read the contests selected by `sql` (pk, sk, contest_id, last_fetched_submission_id)
+/// together with their original problems.
+fn select_contests(
+    conn: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<AdtContestRecord>, DdbError> {
+    let mut stmt = conn.prepare_cached(sql)?;
+    let rows = stmt
+        .query_map(params, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<i64>>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    rows.into_iter()
+        .map(|(pk, sk, contest_id, last_fetched_submission_id)| {
+            let problem_ids = select_contest_problems(conn, &pk, &sk)?;
+            Ok(AdtContestRecord {
+                pk,
+                sk,
+                contest_id: parse_stored::<ContestId>(contest_id)?,
+                last_fetched_submission_id: last_fetched_submission_id
+                    .map(|id| SubmissionId::new(id as u64)),
+                problem_ids,
+            })
+        })
+        .collect()
+}
+
+/// Insert or replace a contest together with its original problems.
+fn upsert_contest(tx: &Transaction<'_>, record: &AdtContestRecord) -> Result<(), DdbError> {
+    tx.execute(
+        "INSERT INTO adt_contests (pk, sk, contest_id, last_fetched_submission_id)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT (pk, sk) DO UPDATE SET
+             contest_id = excluded.contest_id,
+             last_fetched_submission_id = excluded.last_fetched_submission_id",
+        params![
+            record.pk,
+            record.sk,
+            record.contest_id.as_str(),
+            record.last_fetched_submission_id.map(|id| id.get() as i64),
+        ],
+    )?;
+
+    tx.execute(
+        "DELETE FROM adt_contest_problems WHERE pk = ?1 AND sk = ?2",
+        [&record.pk, &record.sk],
+    )?;
+    let mut insert = tx.prepare_cached(
+        "INSERT INTO adt_contest_problems (pk, sk, position, problem_id) VALUES (?1, ?2, ?3, ?4)",
+    )?;
+    for (position, problem_id) in record.problem_ids.iter().enumerate() {
+        insert.execute(params![
+            record.pk,
+            record.sk,
+            position as i64,
+            problem_id.as_str()
+        ])?;
+    }
+
+    Ok(())
+}
+
+impl AcStore for SqliteStore {
+    async fn get_user_ac_problems(
+        &self,
+        user_id: &UserId,
+    ) -> Result<UserAcProblemRecord, DdbError> {
+        select_user_ac_problems(&self.conn(), user_id)?.ok_or(DdbError::NotFound)
+    }
+
+    async fn batch_get_user_ac_problems(
+        &self,
+        user_ids: Vec<UserId>,
+    ) -> Result<HashMap<UserId, UserAcProblemRecord>, DdbError> {
+        let conn = self.conn();
+        let mut result_map = HashMap::new();
+        for user_id in user_ids {
+            if let Some(record) = select_user_ac_problems(&conn, &user_id)? {
+                result_map.insert(user_id, record);
+            }
+        }
+        Ok(result_map)
+    }
+
+    async fn add_ac_problems(
+        &self,
+        user_id: &UserId,
+        problems: BTreeMap<ProblemId, Option<FirstAc>>,
+    ) -> Result<(), DdbError> {
+        let mut conn = self.conn();
+        // Read and write in one immediate transaction so other connections cannot interleave
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let mut record = UserAcProblemRecord::new(user_id, problems);
+        if let Some(current) = select_user_ac_problems(&tx, user_id)? {
+            record.merge_ac_problems_from(&current);
+        }
+        replace_user_ac_problems(&tx, &record)?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+impl ContestStore for SqliteStore {
+    async fn get_contests(
+        &self,
+        max_items: Option<usize>,
+    ) -> Result<Vec<AdtContestRecord>, DdbError> {
+        // A negative LIMIT means no limit
+        let limit = max_items.map_or(-1, |max| max as i64);
+        select_contests(
+            &self.conn(),
+            "SELECT pk, sk, contest_id, last_fetched_submission_id FROM adt_contests
+             ORDER BY pk DESC, sk DESC LIMIT ?1",
+            [limit],
+        )
+    }
+
+    async fn get_contests_by_problem(
+        &self,
+        problem_id: &ProblemId,
+    ) -> Result<Vec<AdtContestRecord>, DdbError> {
+        select_contests(
+            &self.conn(),
+            "SELECT pk, sk, contest_id, last_fetched_submission_id FROM adt_contests c
+             WHERE EXISTS (
+                 SELECT 1 FROM adt_contest_problems p
+                 WHERE p.pk = c.pk AND p.sk = c.sk AND p.problem_id = ?1
+             )
+             ORDER BY pk DESC, sk DESC",
+            [problem_id.as_str()],
+        )
+    }
+
+    async fn batch_write_contests(&self, records: Vec<AdtContestRecord>) -> Result<(), DdbError> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        for record in &records {
+            upsert_contest(&tx, record)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contest(
+        start_epoch_second: u64,
+        contest_id: &str,
+        last_fetched_submission_id: Option<u64>,
+        problem_ids: &[&str],
+    ) -> AdtContestRecord {
+        let contest_id = ContestId::parse(contest_id).unwrap();
+        AdtContestRecord {
+            pk: AdtContestRecord::pk_attr_from_epoch(start_epoch_second)
+                .as_s()
+                .unwrap()
+                .clone(),
+            sk: AdtContestRecord::sk_attr(start_epoch_second, &contest_id)
+                .as_s()
+                .unwrap()
+                .clone(),
+            contest_id,
+            last_fetched_submission_id: last_fetched_submission_id.map(SubmissionId::new),
+            problem_ids: problem_ids
+                .iter()
+                .map(|id| ProblemId::parse(id).unwrap())
+                .collect(),
+        }
+    }
+
+    fn ac_record(user_id: &UserId, ac_problems: &[(&str, u64)]) -> UserAcProblemRecord {
+        UserAcProblemRecord::new(
+            user_id,
+            ac_problems
+                .iter()
+                .map(|(id, epoch_second)| {
+                    let first_ac = FirstAc {
+                        epoch_second: *epoch_second,
+                        contest_id: ContestId::parse("adt_all_20250522_3").unwrap(),
+                        submission_id: SubmissionId::new(*epoch_second),
+                    };
+                    (ProblemId::parse(id).unwrap(), Some(first_ac))
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn open_applies_migrations_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sync.db");
+        SqliteStore::open(&path).unwrap();
+        let store = SqliteStore::open(&path).unwrap();
+
+        let version: u32 = store
+            .conn()
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version as usize, MIGRATIONS.len());
+    }
+
+    #[tokio::test]
+    async fn migrated_legacy_rows_have_no_first_ac() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sync.db");
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(MIGRATIONS[0]).unwrap();
+            conn.pragma_update(None, "user_version", 1).unwrap();
+            conn.execute(
+                "INSERT INTO user_ac_problems (user_id, problem_id) VALUES ('tourist', 'abc407_a')",
+                [],
+            )
+            .unwrap();
+        }
+
+        let store = SqliteStore::open(&path).unwrap();
+        let record = store
+            .get_user_ac_problems(&UserId::parse("tourist").unwrap())
+            .await
+            .unwrap();
+        assert_eq!(record.problem_ids().collect::<Vec<_>>(), ["abc407_a"]);
+        assert_eq!(record.ac_problems.values().next(), Some(&None));
+    }
+
+    #[tokio::test]
+    async fn merged_user_ac_problems_round_trip_with_earliest_first_acs() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        let user_id = UserId::parse("tourist").unwrap();
+        for ac_problems in [
+            &[("abc407_b", 100), ("abc407_a", 100)],
+            &[("abc407_c", 200), ("abc407_a", 200)],
+        ] {
+            store
+                .add_ac_problems(&user_id, ac_record(&user_id, ac_problems).ac_problems)
+                .await
+                .unwrap();
+        }
+
+        let stored = store.get_user_ac_problems(&user_id).await.unwrap();
+        assert_eq!(
+            stored.ac_problems,
+            ac_record(
+                &user_id,
+                &[("abc407_a", 100), ("abc407_b", 100), ("abc407_c", 200)]
+            )
+            .ac_problems
+        );
+
+        let missing = UserId::parse("unknown").unwrap();
+        assert!(matches!(
+            store.get_user_ac_problems(&missing).await,
+            Err(DdbError::NotFound)
+        ));
+        let map = store
+            .batch_get_user_ac_problems(vec![user_id.clone(), missing])
+            .await
+            .unwrap();
+        assert_eq!(map.keys().collect::<Vec<_>>(), [&user_id]);
+    }
+
+    #[tokio::test]
+    async fn contests_keep_cursor_and_problem_order_and_return_newest_first() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        store
+            .batch_write_contests(vec![
+                contest(
+                    1_747_913_400,
+                    "adt_all_20250522_3",
+                    None,
+                    &["abc369_e", "abc301_a"],
+                ),
+                contest(1_749_000_000, "adt_easy_20250604_1", None, &[]),
+            ])
+            .await
+            .unwrap();
+
+        // Rewriting a contest replaces its cursor and problems
+        store
+            .batch_write_contests(vec![contest(
+                1_747_913_400,
+                "adt_all_20250522_3",
+                Some(65417085),
+                &["abc301_a"],
+            )])
+            .await
+            .unwrap();
+
+        let contests = store.get_contests(None).await.unwrap();
+        assert_eq!(contests.len(), 2);
+        assert_eq!(contests[0].contest_id, "adt_easy_20250604_1");
+        assert_eq!(contests[1].contest_id, "adt_all_20250522_3");
+        assert_eq!(
+            contests[1].last_fetched_submission_id,
+            Some(SubmissionId::new(65417085))
+        );
+        assert_eq!(contests[1].problem_ids, ["abc301_a"]);
+
+        assert_eq!(store.get_contests(Some(1)).await.unwrap().len(), 1);
+
+        let contests = store
+            .get_contests_by_problem(&ProblemId::parse("abc301_a").unwrap())
+            .await
+            .unwrap();
+        assert_eq!(contests.len(), 1);
+        assert_eq!(contests[0].contest_id, "adt_all_20250522_3");
+        // The rewrite dropped abc369_e from the contest
+        let contests = store
+            .get_contests_by_problem(&ProblemId::parse("abc369_e").unwrap())
+            .await
+            .unwrap();
+        assert!(contests.is_empty());
+    }
+}
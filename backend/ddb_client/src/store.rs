@@ -0,0 +1,48 @@
+use crate::error::DdbError;
+use crate::models::{AdtContestRecord, FirstAc, UserAcProblemRecord};
+use atcoder_ids::{ProblemId, UserId};
+use std::collections::{BTreeMap, HashMap};
+
+/// Storage for users' AC problem records.
+pub trait AcStore {
+    /// Retrieve a user's AC problems, or `DdbError::NotFound` if the user has none stored.
+    fn get_user_ac_problems(
+        &self,
+        user_id: &UserId,
+    ) -> impl Future<Output = Result<UserAcProblemRecord, DdbError>> + Send;
+
+    /// Retrieve multiple users' AC problems. Users without a record are omitted.
+    fn batch_get_user_ac_problems(
+        &self,
+        user_ids: Vec<UserId>,
+    ) -> impl Future<Output = Result<HashMap<UserId, UserAcProblemRecord>, DdbError>> + Send;
+
+    /// Merge AC problems into the user's stored record, keeping the earliest first AC.
+    /// Safe against concurrent updates of the same user.
+    fn add_ac_problems(
+        &self,
+        user_id: &UserId,
+        problems: BTreeMap<ProblemId, Option<FirstAc>>,
+    ) -> impl Future<Output = Result<(), DdbError>> + Send;
+}
+
+/// Storage for ADT contest records.
+pub trait ContestStore {
+    /// Retrieve ADT contests, newest first (optionally limited by max count).
+    fn get_contests(
+        &self,
+        max_items: Option<usize>,
+    ) -> impl Future<Output = Result<Vec<AdtContestRecord>, DdbError>> + Send;
+
+    /// Retrieve the ADT contests that included the given original problem, newest first.
+    fn get_contests_by_problem(
+        &self,
+        problem_id: &ProblemId,
+    ) -> impl Future<Output = Result<Vec<AdtContestRecord>, DdbError>> + Send;
+
+    /// Write the given records, replacing any stored record with the same keys.
+    fn batch_write_contests(
+        &self,
+        records: Vec<AdtContestRecord>,
+    ) -> impl Future<Output = Result<(), DdbError>> + Send;
+}
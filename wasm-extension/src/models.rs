@@ -6,19 +6,23 @@ pub struct Problem {
     pub contest_id: String,
 }
 impl Problem {
+    /// Parses a problem ID of the form "{prefix}_{index}" (e.g. "abc369_e"),
+    /// following the same rules as the backend's `ProblemId`.
     pub fn new(id: &str) -> Result<Self, &'static str> {
-        // Check if the ID contains an underscore
-        match id.rfind('_') {
-            Some(pos) => {
-                // Extract contest_id (everything before the last underscore)
-                let contest_id = &id[0..pos];
+        if !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err("Invalid ID format: unexpected character");
+        }
 
-                Ok(Self {
-                    id: id.to_string(),
-                    contest_id: contest_id.to_string(),
-                })
-            }
-            None => Err("Invalid ID format: underscore not found"),
+        // The contest part is everything before the last underscore
+        match id.rsplit_once('_') {
+            Some((contest_id, index)) if !contest_id.is_empty() && !index.is_empty() => Ok(Self {
+                id: id.to_string(),
+                contest_id: contest_id.to_string(),
+            }),
+            _ => Err("Invalid ID format: expected {contest}_{index}"),
         }
     }
 }